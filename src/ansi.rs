@@ -0,0 +1,223 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a string is not a valid SGR sequence for the requested type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSgrError {
+    sequence: String,
+}
+
+impl ParseSgrError {
+    pub(crate) fn new(sequence: &str) -> ParseSgrError {
+        ParseSgrError {
+            sequence: sequence.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseSgrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid SGR sequence: {:?}", self.sequence)
+    }
+}
+
+impl Error for ParseSgrError {}
+
+/// Gets the parameters of a single SGR sequence like `"\x1B[38;5;208;1m"`,
+/// an empty sequence `"\x1B[m"` is a reset
+pub(crate) fn sgr_parameters(sequence: &str) -> Result<Vec<u8>, ParseSgrError> {
+    let parameters = sequence
+        .strip_prefix("\x1B[")
+        .and_then(|s| s.strip_suffix('m'))
+        .ok_or_else(|| ParseSgrError::new(sequence))?;
+
+    if parameters.is_empty() {
+        return Ok(vec![0]);
+    }
+
+    parameters
+        .split(';')
+        .map(|parameter| {
+            parameter
+                .parse::<u8>()
+                .map_err(|_| ParseSgrError::new(sequence))
+        })
+        .collect()
+}
+
+/// Piece of a string, either text or a complete escape sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+}
+
+/// Splits a string into text and escape sequences
+pub(crate) fn segments(s: &str) -> Vec<Segment<'_>> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    let mut text_start = 0;
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\x1B' {
+            continue;
+        }
+
+        if start > text_start {
+            segments.push(Segment::Text(&s[text_start..start]));
+        }
+
+        match chars.next() {
+            // CSI: parameters and intermediates, ended by a byte in the range `@` to `~`
+            Some((_, '[')) => {
+                for (_, c) in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ended by BEL or by the string terminator `ESC \`
+            Some((_, ']')) => {
+                while let Some((_, c)) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1B' && chars.peek().is_some_and(|(_, c)| *c == '\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Every other escape sequence is two characters long
+            _ => {}
+        }
+
+        text_start = chars.peek().map_or(s.len(), |(i, _)| *i);
+        segments.push(Segment::Escape(&s[start..text_start]));
+    }
+
+    if text_start < s.len() {
+        segments.push(Segment::Text(&s[text_start..]));
+    }
+    segments
+}
+
+/// Removes all escape sequences from a string, including colors, cursor movement and hyperlinks
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::ansi::strip_ansi;
+///
+/// let l = Apollo::new();
+///
+/// if let Some(line) = l.info("This message is stored without colors") {
+///     assert!(!strip_ansi(&line).contains('\x1B'));
+/// }
+/// ```
+pub fn strip_ansi(s: &str) -> String {
+    segments(s)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// Wraps text in an OSC 8 hyperlink, terminals that support it make the text clickable
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::ansi::hyperlink;
+///
+/// assert_eq!(
+///     "\x1B]8;;file:///main.rs#1\x1B\\main.rs:1\x1B]8;;\x1B\\",
+///     hyperlink("file:///main.rs#1", "main.rs:1")
+/// );
+/// ```
+pub fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1B]8;;{url}\x1B\\{text}\x1B]8;;\x1B\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sgr_parameters() {
+        assert_eq!(Ok(vec![38, 5, 208, 1]), sgr_parameters("\x1B[38;5;208;1m"));
+        assert_eq!(Ok(vec![0]), sgr_parameters("\x1B[m"));
+    }
+
+    #[test]
+    fn test_sgr_parameters_invalid() {
+        assert!(sgr_parameters("31m").is_err());
+        assert!(sgr_parameters("\x1B[31").is_err());
+        assert!(sgr_parameters("\x1B[31;;1m").is_err());
+        assert!(sgr_parameters("\x1B[2Km").is_err());
+        assert!(sgr_parameters("\x1B[38;2;256;0;0m").is_err());
+    }
+
+    #[test]
+    fn test_parse_sgr_error_display() {
+        assert_eq!(
+            "invalid SGR sequence: \"\\u{1b}[2K\"",
+            ParseSgrError::new("\x1B[2K").to_string()
+        );
+    }
+
+    #[test]
+    fn test_strip_sgr() {
+        assert_eq!(
+            "[ INFO  ] | message",
+            strip_ansi("\x1B[34m[ INFO  ]\x1B[0m | \x1B[38;2;1;2;3;1mmessage\x1B[0m")
+        );
+    }
+
+    #[test]
+    fn test_strip_csi() {
+        assert_eq!("abc", strip_ansi("a\x1B[2Kb\x1B[1;1Hc\x1B[?25l"));
+    }
+
+    #[test]
+    fn test_strip_osc() {
+        assert_eq!(
+            "main.rs:1 and title",
+            strip_ansi(
+                "\x1B]8;;file:///main.rs\x1B\\main.rs:1\x1B]8;;\x1B\\ and \x1B]0;window\x07title"
+            )
+        );
+    }
+
+    #[test]
+    fn test_strip_unterminated() {
+        assert_eq!("text", strip_ansi("text\x1B[31"));
+        assert_eq!("text", strip_ansi("text\x1B"));
+        assert_eq!("text", strip_ansi("text\x1Bc"));
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            vec![
+                Segment::Escape("\x1B[31m"),
+                Segment::Text("red"),
+                Segment::Escape("\x1B[0m"),
+                Segment::Text(" é "),
+                Segment::Escape("\x1B]8;;x\x07"),
+            ],
+            segments("\x1B[31mred\x1B[0m é \x1B]8;;x\x07")
+        );
+    }
+
+    #[test]
+    fn test_hyperlink_is_stripped() {
+        assert_eq!(
+            "main.rs:1",
+            strip_ansi(&hyperlink("file:///main.rs#1", "main.rs:1"))
+        );
+    }
+}
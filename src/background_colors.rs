@@ -1,8 +1,17 @@
+use crate::ansi::{ParseSgrError, sgr_parameters};
+use crate::color_conversion::{
+    ansi256_to_rgb, hsl_to_rgb, parse_hex, rgb_to_ansi16, rgb_to_ansi256,
+};
+use crate::color_support::ColorSupport;
 use crate::font_mode::FontMode;
 use crate::foreground_colors::ForegroundColors;
+use crate::names::{ColorName, ParseNameError};
+use crate::style::Style;
 use std::fmt;
 use std::ops::Add;
+use std::str::FromStr;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BackgroundColors {
     Black(&'static str),
     Red(&'static str),
@@ -21,6 +30,8 @@ pub enum BackgroundColors {
     BrightMagenta(&'static str),
     BrightCyan(&'static str),
     BrightWhite(&'static str),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
 }
 
 impl BackgroundColors {
@@ -56,6 +67,7 @@ impl BackgroundColors {
         BackgroundColors::White("\x1B[47m")
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         BackgroundColors::Default("\x1B[49m")
     }
@@ -91,6 +103,172 @@ impl BackgroundColors {
     pub fn bright_white() -> Self {
         BackgroundColors::BrightWhite("\x1B[107m")
     }
+
+    /// Creates a color from the 256-color palette
+    pub fn indexed(index: u8) -> Self {
+        BackgroundColors::Indexed(index)
+    }
+
+    /// Creates a 24-bit truecolor
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        BackgroundColors::Rgb(red, green, blue)
+    }
+
+    /// Creates a truecolor from a hex string like `"#ff8800"` or `"#f80"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::background_colors::BackgroundColors;
+    ///
+    /// let orange = BackgroundColors::from_hex("#ff8800").unwrap();
+    ///
+    /// assert_eq!("\x1B[48;2;255;136;0m", orange.to_string());
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let (red, green, blue) = parse_hex(hex)?;
+        Some(BackgroundColors::Rgb(red, green, blue))
+    }
+
+    /// Creates a truecolor from a hue in degrees and a saturation and lightness between 0 and 1
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let (red, green, blue) = hsl_to_rgb(hue, saturation, lightness);
+        BackgroundColors::Rgb(red, green, blue)
+    }
+
+    /// Creates a color from its name, like `"bright_red"`, `"default"`, `"indexed(208)"` or `"#ff8800"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::background_colors::BackgroundColors;
+    ///
+    /// assert_eq!(Ok(BackgroundColors::bright_red()), BackgroundColors::from_name("bright_red"));
+    /// assert_eq!(Ok(BackgroundColors::indexed(208)), BackgroundColors::from_name("indexed(208)"));
+    /// ```
+    pub fn from_name(name: &str) -> Result<Self, ParseNameError> {
+        match ColorName::parse(name) {
+            Some(ColorName::Default) => Ok(Self::default()),
+            Some(ColorName::Ansi16(index)) => Ok(Self::from_ansi16(index)),
+            Some(ColorName::Indexed(index)) => Ok(BackgroundColors::Indexed(index)),
+            Some(ColorName::Rgb(red, green, blue)) => Ok(BackgroundColors::Rgb(red, green, blue)),
+            None => Err(ParseNameError::new(name)),
+        }
+    }
+
+    /// Gets the name of the color, which `from_name` parses back to the same color
+    pub fn name(&self) -> String {
+        let color = match self {
+            BackgroundColors::Indexed(index) => ColorName::Indexed(*index),
+            BackgroundColors::Rgb(red, green, blue) => ColorName::Rgb(*red, *green, *blue),
+            _ => match self.parameters().parse::<u8>().unwrap_or(49) {
+                code @ 40..=47 => ColorName::Ansi16(code - 40),
+                code @ 100..=107 => ColorName::Ansi16(code - 100 + 8),
+                _ => ColorName::Default,
+            },
+        };
+        color.to_string()
+    }
+
+    /// Creates one of the 16 basic colors, 0 to 7 are the normal and 8 to 15 the bright colors
+    fn from_ansi16(index: u8) -> Self {
+        match index {
+            0 => Self::black(),
+            1 => Self::red(),
+            2 => Self::green(),
+            3 => Self::yellow(),
+            4 => Self::blue(),
+            5 => Self::magenta(),
+            6 => Self::cyan(),
+            7 => Self::white(),
+            8 => Self::bright_black(),
+            9 => Self::bright_red(),
+            10 => Self::bright_green(),
+            11 => Self::bright_yellow(),
+            12 => Self::bright_blue(),
+            13 => Self::bright_magenta(),
+            14 => Self::bright_cyan(),
+            _ => Self::bright_white(),
+        }
+    }
+
+    /// Parses the color at the start of a list of SGR parameters,
+    /// returns the color and the number of parameters it used
+    pub(crate) fn from_parameters(parameters: &[u8]) -> Option<(Self, usize)> {
+        match *parameters {
+            [code @ 40..=47, ..] => Some((Self::from_ansi16(code - 40), 1)),
+            [49, ..] => Some((Self::default(), 1)),
+            [code @ 100..=107, ..] => Some((Self::from_ansi16(code - 100 + 8), 1)),
+            [48, 5, index, ..] => Some((BackgroundColors::Indexed(index), 3)),
+            [48, 2, red, green, blue, ..] => Some((BackgroundColors::Rgb(red, green, blue), 5)),
+            _ => None,
+        }
+    }
+
+    /// Converts the color to the nearest color the terminal supports,
+    /// returns None if the terminal does not support colors
+    pub fn downsample(self, support: ColorSupport) -> Option<Self> {
+        match (self, support) {
+            (_, ColorSupport::None) => None,
+            (BackgroundColors::Rgb(red, green, blue), ColorSupport::Ansi256) => Some(
+                BackgroundColors::Indexed(rgb_to_ansi256((red, green, blue))),
+            ),
+            (BackgroundColors::Rgb(red, green, blue), ColorSupport::Basic) => {
+                Some(Self::from_ansi16(rgb_to_ansi16((red, green, blue))))
+            }
+            (BackgroundColors::Indexed(index), ColorSupport::Basic) => {
+                Some(Self::from_ansi16(rgb_to_ansi16(ansi256_to_rgb(index))))
+            }
+            (color, _) => Some(color),
+        }
+    }
+
+    /// Gets the RGB value of the color, basic colors use the default xterm palette.
+    /// Returns None for the default color, as that depends on the terminal
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            BackgroundColors::Indexed(index) => Some(ansi256_to_rgb(*index)),
+            BackgroundColors::Rgb(red, green, blue) => Some((*red, *green, *blue)),
+            BackgroundColors::Default(_) => None,
+            _ => {
+                let code: u8 = self.parameters().parse().ok()?;
+                match code {
+                    40..=47 => Some(ansi256_to_rgb(code - 40)),
+                    100..=107 => Some(ansi256_to_rgb(code - 100 + 8)),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Gets the SGR parameters of the color, without the escape sequence around them
+    pub fn parameters(&self) -> String {
+        match self {
+            BackgroundColors::Indexed(index) => format!("48;5;{}", index),
+            BackgroundColors::Rgb(red, green, blue) => format!("48;2;{};{};{}", red, green, blue),
+            _ => {
+                let sequence = self.to_string();
+                sequence
+                    .trim_start_matches("\x1B[")
+                    .trim_end_matches('m')
+                    .to_string()
+            }
+        }
+    }
+}
+
+impl FromStr for BackgroundColors {
+    type Err = ParseSgrError;
+
+    /// Parses a single SGR sequence like `"\x1B[41m"` or `"\x1B[48;5;208m"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parameters = sgr_parameters(s)?;
+
+        match Self::from_parameters(&parameters) {
+            Some((color, length)) if length == parameters.len() => Ok(color),
+            _ => Err(ParseSgrError::new(s)),
+        }
+    }
 }
 
 impl fmt::Display for BackgroundColors {
@@ -113,62 +291,43 @@ impl fmt::Display for BackgroundColors {
             BackgroundColors::BrightMagenta(s) => write!(f, "{}", s),
             BackgroundColors::BrightCyan(s) => write!(f, "{}", s),
             BackgroundColors::BrightWhite(s) => write!(f, "{}", s),
+            BackgroundColors::Indexed(index) => write!(f, "\x1B[48;5;{}m", index),
+            BackgroundColors::Rgb(red, green, blue) => {
+                write!(f, "\x1B[48;2;{};{};{}m", red, green, blue)
+            }
         }
     }
 }
 
 impl Add<ForegroundColors> for BackgroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: ForegroundColors) -> Self::Output {
-        let background_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let foreground_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", background_color, foreground_color)
+        Style::from(self) + rhs
     }
 }
 
 impl Add<FontMode> for BackgroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: FontMode) -> Self::Output {
-        let background_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let font_mode = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", background_color, font_mode)
+        Style::from(self) + rhs
     }
 }
 
 impl Add<BackgroundColors> for BackgroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: BackgroundColors) -> Self::Output {
-        let background_color_lhs = self.to_string().replace("\x1B[", "").replace("m", "");
-        let background_color_rhs = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", background_color_lhs, background_color_rhs)
-    }
-}
-
-impl Add<String> for BackgroundColors {
-    type Output = String;
-
-    fn add(self, rhs: String) -> Self::Output {
-        let background_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let string = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", background_color, string)
+        Style::from(self) + rhs
     }
 }
 
-impl Add<BackgroundColors> for String {
-    type Output = String;
+impl Add<Style> for BackgroundColors {
+    type Output = Style;
 
-    fn add(self, rhs: BackgroundColors) -> Self::Output {
-        let string = self.to_string().replace("\x1B[", "").replace("m", "");
-        let background_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", string, background_color)
+    fn add(self, rhs: Style) -> Self::Output {
+        Style::from(self) + rhs
     }
 }
 
@@ -261,12 +420,88 @@ mod tests {
         assert_eq!("\x1B[107m", BackgroundColors::bright_white().to_string());
     }
 
+    #[test]
+    fn test_indexed() {
+        assert_eq!("\x1B[48;5;208m", BackgroundColors::indexed(208).to_string());
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(
+            "\x1B[48;2;1;2;3m",
+            BackgroundColors::rgb(1, 2, 3).to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(
+            "\x1B[48;2;255;136;0m",
+            BackgroundColors::from_hex("#ff8800").unwrap().to_string()
+        );
+        assert!(BackgroundColors::from_hex("orange").is_none());
+    }
+
+    #[test]
+    fn test_from_hsl() {
+        assert_eq!(
+            "\x1B[48;2;0;0;255m",
+            BackgroundColors::from_hsl(240.0, 1.0, 0.5).to_string()
+        );
+    }
+
+    #[test]
+    fn test_downsample_truecolor() {
+        let color = BackgroundColors::rgb(255, 136, 0).downsample(ColorSupport::TrueColor);
+
+        assert_eq!("\x1B[48;2;255;136;0m", color.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_ansi256() {
+        let color = BackgroundColors::rgb(255, 136, 0).downsample(ColorSupport::Ansi256);
+        let indexed = BackgroundColors::indexed(17).downsample(ColorSupport::Ansi256);
+
+        assert_eq!("\x1B[48;5;208m", color.unwrap().to_string());
+        assert_eq!("\x1B[48;5;17m", indexed.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_basic() {
+        let color = BackgroundColors::rgb(255, 136, 0).downsample(ColorSupport::Basic);
+        let indexed = BackgroundColors::indexed(196).downsample(ColorSupport::Basic);
+        let basic = BackgroundColors::indexed(4).downsample(ColorSupport::Basic);
+        let unchanged = BackgroundColors::cyan().downsample(ColorSupport::Basic);
+
+        assert_eq!("\x1B[43m", color.unwrap().to_string());
+        assert_eq!("\x1B[101m", indexed.unwrap().to_string());
+        assert_eq!("\x1B[44m", basic.unwrap().to_string());
+        assert_eq!("\x1B[46m", unchanged.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_none() {
+        assert!(
+            BackgroundColors::rgb(255, 136, 0)
+                .downsample(ColorSupport::None)
+                .is_none()
+        );
+        assert!(
+            BackgroundColors::red()
+                .downsample(ColorSupport::None)
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_background_color_add_foreground_color() {
         let background_color = BackgroundColors::red();
         let foreground_color = ForegroundColors::bright_yellow();
 
-        assert_eq!("\x1B[41;93m", background_color + foreground_color);
+        assert_eq!(
+            "\x1B[93;41m",
+            (background_color + foreground_color).to_string()
+        );
     }
 
     #[test]
@@ -274,7 +509,10 @@ mod tests {
         let background_color_one = BackgroundColors::red();
         let background_color_two = BackgroundColors::bright_yellow();
 
-        assert_eq!("\x1B[41;103m", background_color_one + background_color_two);
+        assert_eq!(
+            "\x1B[103m",
+            (background_color_one + background_color_two).to_string()
+        );
     }
 
     #[test]
@@ -282,22 +520,116 @@ mod tests {
         let background_color = BackgroundColors::red();
         let font_mode = FontMode::italic();
 
-        assert_eq!("\x1B[41;3m", background_color + font_mode);
+        assert_eq!("\x1B[41;3m", (background_color + font_mode).to_string());
     }
 
     #[test]
-    fn test_background_color_add_string() {
+    fn test_background_color_add_style() {
         let background_color = BackgroundColors::red();
-        let string = FontMode::italic() + ForegroundColors::bright_blue();
+        let style = FontMode::italic() + ForegroundColors::bright_blue();
 
-        assert_eq!("\x1B[41;3;94m", background_color + string);
+        assert_eq!("\x1B[94;41;3m", (background_color + style).to_string());
     }
 
     #[test]
-    fn test_string_add_background_color() {
-        let string = FontMode::italic() + ForegroundColors::bright_blue();
+    fn test_style_add_background_color() {
+        let style = FontMode::italic() + BackgroundColors::bright_blue();
         let background_color = BackgroundColors::red();
 
-        assert_eq!("\x1B[3;94;41m", string + background_color);
+        assert_eq!("\x1B[41;3m", (style + background_color).to_string());
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!("41", BackgroundColors::red().parameters());
+        assert_eq!("48;5;208", BackgroundColors::indexed(208).parameters());
+        assert_eq!("48;2;1;2;3", BackgroundColors::rgb(1, 2, 3).parameters());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(BackgroundColors::red()), "\x1B[41m".parse());
+        assert_eq!(Ok(BackgroundColors::default()), "\x1B[49m".parse());
+        assert_eq!(Ok(BackgroundColors::bright_cyan()), "\x1B[106m".parse());
+        assert_eq!(Ok(BackgroundColors::indexed(208)), "\x1B[48;5;208m".parse());
+        assert_eq!(
+            Ok(BackgroundColors::rgb(1, 2, 3)),
+            "\x1B[48;2;1;2;3m".parse()
+        );
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        for index in 0..16 {
+            let color = BackgroundColors::from_ansi16(index);
+            assert_eq!(Ok(color.clone()), color.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!("\x1B[41;1m".parse::<BackgroundColors>().is_err());
+        assert!("\x1B[48;5m".parse::<BackgroundColors>().is_err());
+        assert!("\x1B[38;5;208m".parse::<BackgroundColors>().is_err());
+        assert!("41".parse::<BackgroundColors>().is_err());
+    }
+
+    #[test]
+    fn test_to_rgb() {
+        assert_eq!(Some((205, 0, 0)), BackgroundColors::red().to_rgb());
+        assert_eq!(
+            Some((92, 92, 255)),
+            BackgroundColors::bright_blue().to_rgb()
+        );
+        assert_eq!(Some((255, 135, 0)), BackgroundColors::indexed(208).to_rgb());
+        assert_eq!(Some((1, 2, 3)), BackgroundColors::rgb(1, 2, 3).to_rgb());
+        assert_eq!(None, BackgroundColors::default().to_rgb());
+    }
+
+    #[test]
+    fn test_to_rgb_of_unknown_code() {
+        assert_eq!(None, BackgroundColors::Black("\x1B[31m").to_rgb());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Ok(BackgroundColors::black()),
+            BackgroundColors::from_name("black")
+        );
+        assert_eq!(
+            Ok(BackgroundColors::default()),
+            BackgroundColors::from_name("default")
+        );
+        assert_eq!(
+            Ok(BackgroundColors::bright_magenta()),
+            BackgroundColors::from_name("BRIGHT_MAGENTA")
+        );
+        assert_eq!(
+            Ok(BackgroundColors::rgb(255, 136, 0)),
+            BackgroundColors::from_name("#ff8800")
+        );
+        assert!(BackgroundColors::from_name("orange").is_err());
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        let colors = [
+            BackgroundColors::black(),
+            BackgroundColors::white(),
+            BackgroundColors::default(),
+            BackgroundColors::bright_black(),
+            BackgroundColors::bright_white(),
+            BackgroundColors::indexed(208),
+            BackgroundColors::rgb(1, 2, 3),
+        ];
+
+        for color in colors {
+            assert_eq!(
+                Ok(color.clone()),
+                BackgroundColors::from_name(&color.name())
+            );
+        }
+        assert_eq!("bright_yellow", BackgroundColors::bright_yellow().name());
     }
 }
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Source of the timestamps Apollo puts in front of every log line
+pub trait Clock: Send + Sync {
+    /// Gets the current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Gets the monotonic time that has passed since the clock was created
+    fn elapsed(&self) -> std::time::Duration;
+}
+
+/// Clock that reads the time from the operating system
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// Creates a new clock, elapsed time is measured from this moment
+    pub fn new() -> SystemClock {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn elapsed(&self) -> std::time::Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// Clock that only moves when told to, useful for deterministic tests
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::clock::{Clock, ManualClock};
+/// use chrono::{Duration, TimeZone, Utc};
+///
+/// let clock = ManualClock::new(Utc.with_ymd_and_hms(2025, 6, 29, 16, 49, 8).unwrap());
+/// clock.advance(Duration::milliseconds(250));
+///
+/// assert_eq!("06/29/25 16:49:08.250", clock.now().format("%D %H:%M:%S%.3f").to_string());
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    time: Mutex<DateTime<Utc>>,
+    elapsed: Mutex<std::time::Duration>,
+}
+
+impl ManualClock {
+    /// Creates a new clock frozen at the given time
+    pub fn new(time: DateTime<Utc>) -> ManualClock {
+        ManualClock {
+            time: Mutex::new(time),
+            elapsed: Mutex::new(std::time::Duration::ZERO),
+        }
+    }
+
+    /// Moves the clock to the given time, this does not count towards the elapsed time
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time.lock().unwrap() = time;
+    }
+
+    /// Moves the clock forward by the given duration
+    ///
+    /// Negative durations only move the wall-clock time, as elapsed time can never go backwards
+    pub fn advance(&self, duration: Duration) {
+        *self.time.lock().unwrap() += duration;
+        *self.elapsed.lock().unwrap() += duration.to_std().unwrap_or_default();
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time.lock().unwrap()
+    }
+
+    fn elapsed(&self) -> std::time::Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_manual_clock_is_frozen() {
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+
+        assert_eq!(clock.now(), clock.now());
+    }
+
+    #[test]
+    fn test_manual_clock_advance() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        clock.advance(Duration::seconds(90));
+
+        assert_eq!(start + Duration::seconds(90), clock.now());
+    }
+
+    #[test]
+    fn test_manual_clock_set() {
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        let later = Utc.with_ymd_and_hms(2030, 12, 31, 23, 59, 59).unwrap();
+        clock.set(later);
+
+        assert_eq!(later, clock.now());
+    }
+
+    #[test]
+    fn test_manual_clock_elapsed() {
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        clock.advance(Duration::milliseconds(1500));
+        clock.set(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+        clock.advance(Duration::seconds(-10));
+
+        assert_eq!(std::time::Duration::from_millis(1500), clock.elapsed());
+    }
+
+    #[test]
+    fn test_system_clock_moves_forward() {
+        let clock = SystemClock::new();
+        let first = clock.now();
+        let first_elapsed = clock.elapsed();
+
+        assert!(clock.now() >= first);
+        assert!(clock.elapsed() >= first_elapsed);
+    }
+}
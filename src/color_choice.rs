@@ -0,0 +1,113 @@
+use std::env;
+use std::io::{IsTerminal, stderr, stdout};
+
+/// Whether Apollo should print ANSI escape sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Always print colors, even when the output is redirected
+    Always,
+    /// Never print colors
+    Never,
+    /// Print colors when the output is a terminal, honoring `NO_COLOR`, `CLICOLOR`,
+    /// `CLICOLOR_FORCE` and `FORCE_COLOR`
+    Auto,
+}
+
+/// Console stream a log line is printed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            Self::Stdout => stdout().is_terminal(),
+            Self::Stderr => stderr().is_terminal(),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// Checks if colors should be printed to the given stream
+    pub fn use_colors(&self, stream: Stream) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => Self::detect(|key| env::var(key).ok(), stream.is_terminal()),
+        }
+    }
+
+    /// Decides whether colors should be printed based on the environment
+    ///
+    /// Forcing colors takes precedence over disabling them, after which
+    /// colors are only printed when the output is a terminal
+    fn detect(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+        // Empty values and "0" count as unset for the force variables
+        let is_enabled = |key: &str| {
+            var(key).is_some_and(|value| !value.is_empty() && value != "0" && value != "false")
+        };
+
+        if is_enabled("CLICOLOR_FORCE") || is_enabled("FORCE_COLOR") {
+            return true;
+        }
+
+        // See https://no-color.org, any non-empty value disables colors
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return false;
+        }
+
+        if var("CLICOLOR").is_some_and(|value| value == "0") {
+            return false;
+        }
+
+        is_terminal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::env;
+
+    #[test]
+    fn test_always_and_never() {
+        assert!(ColorChoice::Always.use_colors(Stream::Stdout));
+        assert!(!ColorChoice::Never.use_colors(Stream::Stderr));
+    }
+
+    #[test]
+    fn test_detect_terminal() {
+        assert!(ColorChoice::detect(env(&[]), true));
+        assert!(!ColorChoice::detect(env(&[]), false));
+    }
+
+    #[test]
+    fn test_detect_no_color() {
+        assert!(!ColorChoice::detect(env(&[("NO_COLOR", "1")]), true));
+        assert!(ColorChoice::detect(env(&[("NO_COLOR", "")]), true));
+    }
+
+    #[test]
+    fn test_detect_clicolor() {
+        assert!(!ColorChoice::detect(env(&[("CLICOLOR", "0")]), true));
+        assert!(ColorChoice::detect(env(&[("CLICOLOR", "1")]), true));
+        assert!(!ColorChoice::detect(env(&[("CLICOLOR", "1")]), false));
+    }
+
+    #[test]
+    fn test_detect_force() {
+        assert!(ColorChoice::detect(env(&[("CLICOLOR_FORCE", "1")]), false));
+        assert!(ColorChoice::detect(env(&[("FORCE_COLOR", "3")]), false));
+        assert!(!ColorChoice::detect(env(&[("FORCE_COLOR", "0")]), false));
+        assert!(!ColorChoice::detect(
+            env(&[("FORCE_COLOR", "false")]),
+            false
+        ));
+        assert!(ColorChoice::detect(
+            env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            false
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+/// Parses a hex color in `#rrggbb` or `#rgb` format, the `#` is optional
+pub(crate) fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, len: usize| u8::from_str_radix(&digits[i * len..(i + 1) * len], 16);
+    match digits.len() {
+        6 => Some((
+            channel(0, 2).ok()?,
+            channel(1, 2).ok()?,
+            channel(2, 2).ok()?,
+        )),
+        // Every digit is repeated, e.g. `#f80` is `#ff8800`
+        3 => Some((
+            channel(0, 1).ok()? * 17,
+            channel(1, 1).ok()? * 17,
+            channel(2, 1).ok()? * 17,
+        )),
+        _ => None,
+    }
+}
+
+/// Converts a hue in degrees and a saturation and lightness between 0 and 1 to RGB
+pub(crate) fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (u8, u8, u8) {
+    let hue = hue.rem_euclid(360.0);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let channel = |value: f32| ((value + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// Default xterm values of the 16 basic colors, in SGR order
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel values of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Squared euclidean distance between two colors
+fn distance(lhs: (u8, u8, u8), rhs: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(lhs.0, rhs.0) + channel(lhs.1, rhs.1) + channel(lhs.2, rhs.2)
+}
+
+/// Gets the RGB value of an entry in the 256-color palette
+pub(crate) fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16_PALETTE[index as usize],
+        16..=231 => {
+            let cube = index - 16;
+            (
+                CUBE_LEVELS[(cube / 36) as usize],
+                CUBE_LEVELS[(cube / 6 % 6) as usize],
+                CUBE_LEVELS[(cube % 6) as usize],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Gets the nearest entry in the 256-color palette, skipping the 16 basic colors as
+/// terminals often change those
+pub(crate) fn rgb_to_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let nearest_level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap() as u8
+    };
+    let cube = 16 + 36 * nearest_level(rgb.0) + 6 * nearest_level(rgb.1) + nearest_level(rgb.2);
+
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if distance(rgb, ansi256_to_rgb(gray)) < distance(rgb, ansi256_to_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// Gets the nearest of the 16 basic colors, 0 to 7 are the normal and 8 to 15 the bright colors
+pub(crate) fn rgb_to_ansi16(rgb: (u8, u8, u8)) -> u8 {
+    (0..ANSI16_PALETTE.len())
+        .min_by_key(|&i| distance(rgb, ANSI16_PALETTE[i]))
+        .unwrap() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Some((255, 136, 0)), parse_hex("#ff8800"));
+        assert_eq!(Some((255, 136, 0)), parse_hex("FF8800"));
+        assert_eq!(Some((255, 136, 0)), parse_hex("#f80"));
+    }
+
+    #[test]
+    fn test_parse_hex_invalid() {
+        assert_eq!(None, parse_hex("#ff880"));
+        assert_eq!(None, parse_hex("#gg8800"));
+        assert_eq!(None, parse_hex("#+f8800"));
+        assert_eq!(None, parse_hex(""));
+    }
+
+    #[test]
+    fn test_hsl_to_rgb() {
+        assert_eq!((255, 0, 0), hsl_to_rgb(0.0, 1.0, 0.5));
+        assert_eq!((0, 255, 0), hsl_to_rgb(120.0, 1.0, 0.5));
+        assert_eq!((0, 0, 255), hsl_to_rgb(240.0, 1.0, 0.5));
+        assert_eq!((255, 0, 0), hsl_to_rgb(360.0, 1.0, 0.5));
+        assert_eq!((255, 128, 0), hsl_to_rgb(30.0, 1.0, 0.5));
+        assert_eq!((128, 128, 128), hsl_to_rgb(200.0, 0.0, 0.5));
+        assert_eq!((255, 255, 255), hsl_to_rgb(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_ansi256_to_rgb() {
+        assert_eq!((205, 0, 0), ansi256_to_rgb(1));
+        assert_eq!((0, 0, 0), ansi256_to_rgb(16));
+        assert_eq!((255, 135, 0), ansi256_to_rgb(208));
+        assert_eq!((255, 255, 255), ansi256_to_rgb(231));
+        assert_eq!((8, 8, 8), ansi256_to_rgb(232));
+        assert_eq!((238, 238, 238), ansi256_to_rgb(255));
+    }
+
+    #[test]
+    fn test_rgb_to_ansi256() {
+        assert_eq!(208, rgb_to_ansi256((255, 136, 0)));
+        assert_eq!(196, rgb_to_ansi256((255, 0, 0)));
+        assert_eq!(16, rgb_to_ansi256((0, 0, 0)));
+        assert_eq!(231, rgb_to_ansi256((255, 255, 255)));
+        assert_eq!(244, rgb_to_ansi256((128, 128, 128)));
+        assert_eq!(235, rgb_to_ansi256((38, 38, 40)));
+    }
+
+    #[test]
+    fn test_rgb_to_ansi256_round_trip() {
+        for index in 16..=255 {
+            assert_eq!(index, rgb_to_ansi256(ansi256_to_rgb(index)));
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_ansi16() {
+        assert_eq!(0, rgb_to_ansi16((10, 10, 10)));
+        assert_eq!(1, rgb_to_ansi16((180, 20, 10)));
+        assert_eq!(9, rgb_to_ansi16((255, 30, 30)));
+        assert_eq!(12, rgb_to_ansi16((90, 100, 250)));
+        assert_eq!(15, rgb_to_ansi16((250, 250, 250)));
+        assert_eq!(8, rgb_to_ansi16((128, 128, 128)));
+    }
+}
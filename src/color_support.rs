@@ -0,0 +1,230 @@
+use crate::background_colors::BackgroundColors;
+use crate::foreground_colors::ForegroundColors;
+use std::env;
+
+/// Colors a terminal is able to display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    /// No colors, font modes like bold and italic are still printed
+    None,
+    /// The 16 basic colors
+    Basic,
+    /// The 256-color palette
+    Ansi256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detects the color support of the terminal from `COLORTERM` and `TERM`
+    pub fn detect() -> ColorSupport {
+        Self::from_env(|key| env::var(key).ok())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> ColorSupport {
+        if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+            return ColorSupport::TrueColor;
+        }
+
+        match var("TERM") {
+            Some(term) if term == "dumb" => ColorSupport::None,
+            Some(term) if term.ends_with("-direct") || term.contains("truecolor") => {
+                ColorSupport::TrueColor
+            }
+            Some(term) if term.contains("256color") => ColorSupport::Ansi256,
+            _ => ColorSupport::Basic,
+        }
+    }
+}
+
+/// Rewrites every color in the SGR sequences of a string to the nearest color the terminal
+/// supports, all other characters are left as is
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::color_support::{ColorSupport, downsample_sequence};
+/// use crate::apollo_logger::font_mode::FontMode;
+/// use crate::apollo_logger::foreground_colors::ForegroundColors;
+///
+/// let orange = ForegroundColors::from_hex("#ff8800").unwrap() + FontMode::bold();
+///
+/// assert_eq!("\x1B[38;5;208;1m", downsample_sequence(&orange.to_string(), ColorSupport::Ansi256));
+/// assert_eq!("\x1B[33;1m", downsample_sequence(&orange.to_string(), ColorSupport::Basic));
+/// assert_eq!("\x1B[1m", downsample_sequence(&orange.to_string(), ColorSupport::None));
+/// ```
+pub fn downsample_sequence(s: &str, support: ColorSupport) -> String {
+    if support == ColorSupport::TrueColor {
+        return s.to_string();
+    }
+
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("\x1B[") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        // Only sequences made of parameters and ending in `m` are SGR sequences
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != ';')
+            .unwrap_or(rest.len());
+        if !rest[end..].starts_with('m') {
+            result.push_str("\x1B[");
+            continue;
+        }
+
+        let parameters = downsample_parameters(&rest[..end], support);
+        if !parameters.is_empty() || end == 0 {
+            result.push_str(&format!("\x1B[{}m", parameters));
+        }
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Downsamples the `38;5;n`, `38;2;r;g;b`, `48;5;n` and `48;2;r;g;b` parameters of an SGR sequence
+fn downsample_parameters(parameters: &str, support: ColorSupport) -> String {
+    let codes: Vec<&str> = parameters.split(';').collect();
+    let mut result: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < codes.len() {
+        let number = |offset: usize| codes.get(i + offset).and_then(|c| c.parse::<u8>().ok());
+
+        let foreground =
+            |color: ForegroundColors| color.downsample(support).map(|color| color.parameters());
+        let background =
+            |color: BackgroundColors| color.downsample(support).map(|color| color.parameters());
+        let rgb = || match (number(2), number(3), number(4)) {
+            (Some(red), Some(green), Some(blue)) => Some((red, green, blue)),
+            _ => None,
+        };
+
+        let extended = match (codes[i], number(1)) {
+            ("38", Some(5)) => number(2).map(|n| (foreground(ForegroundColors::indexed(n)), 3)),
+            ("38", Some(2)) => {
+                rgb().map(|(r, g, b)| (foreground(ForegroundColors::rgb(r, g, b)), 5))
+            }
+            ("48", Some(5)) => number(2).map(|n| (background(BackgroundColors::indexed(n)), 3)),
+            ("48", Some(2)) => {
+                rgb().map(|(r, g, b)| (background(BackgroundColors::rgb(r, g, b)), 5))
+            }
+            _ => None,
+        };
+
+        match extended {
+            Some((downsampled, length)) => {
+                if let Some(parameters) = downsampled {
+                    result.push(parameters);
+                }
+                i += length;
+            }
+            None => {
+                // Basic colors are always supported, but not when colors are disabled entirely
+                let is_color = codes[i]
+                    .parse::<u8>()
+                    .is_ok_and(|c| matches!(c, 30..=39 | 40..=49 | 90..=97 | 100..=107));
+                if !(support == ColorSupport::None && is_color) {
+                    result.push(codes[i].to_string());
+                }
+                i += 1;
+            }
+        }
+    }
+
+    result.join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_mode::FontMode;
+    use crate::test_utils::env;
+
+    #[test]
+    fn test_detect_colorterm() {
+        assert_eq!(
+            ColorSupport::TrueColor,
+            ColorSupport::from_env(env(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]))
+        );
+        assert_eq!(
+            ColorSupport::TrueColor,
+            ColorSupport::from_env(env(&[("COLORTERM", "24bit")]))
+        );
+    }
+
+    #[test]
+    fn test_detect_term() {
+        assert_eq!(
+            ColorSupport::Ansi256,
+            ColorSupport::from_env(env(&[("TERM", "xterm-256color")]))
+        );
+        assert_eq!(
+            ColorSupport::TrueColor,
+            ColorSupport::from_env(env(&[("TERM", "xterm-direct")]))
+        );
+        assert_eq!(
+            ColorSupport::Basic,
+            ColorSupport::from_env(env(&[("TERM", "xterm")]))
+        );
+        assert_eq!(
+            ColorSupport::None,
+            ColorSupport::from_env(env(&[("TERM", "dumb")]))
+        );
+        assert_eq!(ColorSupport::Basic, ColorSupport::from_env(env(&[])));
+    }
+
+    #[test]
+    fn test_downsample_sequence_truecolor() {
+        let sequence = "\x1B[38;2;255;136;0;48;5;17m";
+
+        assert_eq!(
+            sequence,
+            downsample_sequence(sequence, ColorSupport::TrueColor)
+        );
+    }
+
+    #[test]
+    fn test_downsample_sequence_ansi256() {
+        assert_eq!(
+            "\x1B[38;5;208;48;5;17m",
+            downsample_sequence("\x1B[38;2;255;136;0;48;5;17m", ColorSupport::Ansi256)
+        );
+    }
+
+    #[test]
+    fn test_downsample_sequence_basic() {
+        assert_eq!(
+            "\x1B[33;40;1m",
+            downsample_sequence("\x1B[38;2;255;136;0;48;5;17;1m", ColorSupport::Basic)
+        );
+    }
+
+    #[test]
+    fn test_downsample_sequence_none() {
+        let style = ForegroundColors::rgb(1, 2, 3) + BackgroundColors::red() + FontMode::bold();
+
+        assert_eq!(
+            "\x1B[1m",
+            downsample_sequence(&style.to_string(), ColorSupport::None)
+        );
+        assert_eq!(
+            "",
+            downsample_sequence(&ForegroundColors::red().to_string(), ColorSupport::None)
+        );
+    }
+
+    #[test]
+    fn test_downsample_sequence_keeps_text() {
+        assert_eq!(
+            "a \x1B[91mred\x1B[0m \x1B[2K word",
+            downsample_sequence(
+                "a \x1B[38;5;196mred\x1B[0m \x1B[2K word",
+                ColorSupport::Basic
+            )
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Optional columns about the thread and process that logged a message, printed after the label
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::columns::Columns;
+///
+/// let l = Apollo { columns: Columns::all(), ..Apollo::new() };
+///
+/// // Prints something like `[06/29/25 16:49:08.123] [ INFO  ] | main #1 pid:4242 | main.rs:6 | Started`
+/// l.info("Started");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Columns {
+    /// Name of the current thread, `<unnamed>` for threads without a name
+    pub thread_name: bool,
+    /// Numeric ID of the current thread, like `#3`
+    pub thread_id: bool,
+    /// ID of the process, like `pid:4242`
+    pub process_id: bool,
+}
+
+impl Columns {
+    /// Prints none of the columns
+    pub fn none() -> Columns {
+        Columns::default()
+    }
+
+    /// Prints all columns, for debugging multithreaded code
+    pub fn all() -> Columns {
+        Columns {
+            thread_name: true,
+            thread_id: true,
+            process_id: true,
+        }
+    }
+}
+
+/// Next numeric thread ID, counted from 1 in the order threads first log
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Numeric ID of the current thread, taken from the counter once per thread
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Gets the numeric ID of the current thread, which is unique within the process
+pub fn thread_id() -> u64 {
+    THREAD_ID.with(|id| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_id() {
+        let main = thread_id();
+        let other = std::thread::spawn(thread_id).join().unwrap();
+
+        assert_ne!(0, main);
+        assert_ne!(main, other);
+        assert_eq!(main, thread_id());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::field::Field;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    /// Fields of the current thread, every record includes them
+    static STACK: RefCell<Vec<Field>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes the fields it was created for from the context when dropped
+///
+/// Guards can not be sent to another thread, as the context belongs to the thread.
+/// Guards should be dropped in the reverse order they were created in, which happens
+/// automatically when they are kept in local variables
+#[must_use = "the fields are removed from the context as soon as the guard is dropped"]
+pub struct ContextGuard {
+    count: usize,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        STACK.with_borrow_mut(|stack| stack.truncate(stack.len().saturating_sub(self.count)));
+    }
+}
+
+/// Adds a field to every record logged on this thread until the guard is dropped
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::context;
+///
+/// let l = Apollo::new();
+///
+/// {
+///     let _request = context::push("request_id", "7f3a");
+///     l.info("This message has a request_id field");
+/// }
+/// l.info("This message does not");
+/// ```
+pub fn push(key: impl Into<String>, value: impl Into<Value>) -> ContextGuard {
+    push_fields(vec![Field::new(key, value)])
+}
+
+/// Adds fields to every record logged on this thread until the guard is dropped
+pub fn push_fields(fields: Vec<Field>) -> ContextGuard {
+    let count = fields.len();
+    STACK.with_borrow_mut(|stack| stack.extend(fields));
+
+    ContextGuard {
+        count,
+        _not_send: PhantomData,
+    }
+}
+
+/// Gets the fields of the current context, the oldest first
+pub fn fields() -> Vec<Field> {
+    STACK.with_borrow(|stack| stack.clone())
+}
+
+/// Future that runs with its own context, see [`with_context`]
+pub struct WithContext<F: Future> {
+    fields: Vec<Field>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithContext<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The thread gets the context of the task while it runs, a task can move between
+        // threads and other tasks on the same thread must not see its fields.
+        // Fields the task pushes are kept for its next poll
+        let fields = std::mem::take(&mut self.fields);
+        let previous = STACK.replace(fields);
+        let poll = self.future.as_mut().poll(cx);
+        self.fields = STACK.replace(previous);
+
+        poll
+    }
+}
+
+/// Runs a future with its own context, which starts with the fields of the current context
+/// and the given fields. Guards created inside the future only change its own context
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::context::with_context;
+/// use crate::apollo_logger::field::Field;
+/// use std::sync::Arc;
+///
+/// async fn handle(l: Arc<Apollo>) {
+///     l.info("This message has a request_id field");
+/// }
+///
+/// let l = Arc::new(Apollo::new());
+/// let task = with_context(vec![Field::new("request_id", "7f3a")], handle(l));
+/// ```
+pub fn with_context<F: Future>(fields: Vec<Field>, future: F) -> WithContext<F> {
+    let mut all = self::fields();
+    all.extend(fields);
+
+    WithContext {
+        fields: all,
+        future: Box::pin(future),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Waker;
+
+    /// Polls a future until it is ready, it must not wait for anything
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Future that is pending the first time it is polled
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_push_and_drop() {
+        let outer = push("request_id", "a");
+        {
+            let _inner = push_fields(vec![Field::new("user", 1), Field::new("admin", false)]);
+            assert_eq!(3, fields().len());
+        }
+        assert_eq!(vec![Field::new("request_id", "a")], fields());
+
+        drop(outer);
+        assert!(fields().is_empty());
+    }
+
+    #[test]
+    fn test_threads_have_their_own_context() {
+        let _guard = push("thread", "main");
+
+        let other = std::thread::spawn(fields).join().unwrap();
+        assert!(other.is_empty());
+        assert_eq!(1, fields().len());
+    }
+
+    #[test]
+    fn test_with_context() {
+        let guard = push("request_id", "a");
+
+        let task = with_context(vec![Field::new("task", 1)], async {
+            let before = fields();
+            YieldOnce(false).await;
+            (before, fields())
+        });
+        drop(guard);
+        let _other = push("request_id", "b");
+
+        let (before, after) = block_on(task);
+        let expected = vec![Field::new("request_id", "a"), Field::new("task", 1)];
+        assert_eq!(expected, before);
+        assert_eq!(expected, after);
+        assert_eq!(vec![Field::new("request_id", "b")], fields());
+    }
+
+    #[test]
+    fn test_guard_inside_task() {
+        let task = with_context(Vec::new(), async {
+            let _user = push("user", 1);
+            YieldOnce(false).await;
+            fields()
+        });
+
+        assert_eq!(vec![Field::new("user", 1)], block_on(task));
+        assert!(fields().is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::value::Value;
+use std::fmt;
+
+/// Key-value pair attached to a record
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::field::Field;
+///
+/// assert_eq!("user_id=42", Field::new("user_id", 42).to_string());
+/// assert_eq!("reason=\"bad password\"", Field::new("reason", "bad password").to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub key: String,
+    pub value: Value,
+}
+
+impl Field {
+    pub fn new(key: impl Into<String>, value: impl Into<Value>) -> Field {
+        Field {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Quotes a key or value for logfmt if it is empty or contains spaces, quotes, `=` or control characters
+pub(crate) fn quote(s: &str) -> String {
+    let needs_quotes = s.is_empty()
+        || s.chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '"' || c == '=');
+
+    if needs_quotes {
+        format!("{s:?}")
+    } else {
+        s.to_string()
+    }
+}
+
+/// Prints the field in logfmt, as `key=value`
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", quote(&self.key), quote(&self.value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("ok=true", Field::new("ok", true).to_string());
+        assert_eq!("ratio=0.25", Field::new("ratio", 0.25).to_string());
+        assert_eq!("name=\"\"", Field::new("name", "").to_string());
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!("plain", quote("plain"));
+        assert_eq!("\"a=b\"", quote("a=b"));
+        assert_eq!("\"say \\\"hi\\\"\"", quote("say \"hi\""));
+        assert_eq!("\"line\\nbreak\"", quote("line\nbreak"));
+    }
+}
@@ -1,8 +1,13 @@
+use crate::ansi::{ParseSgrError, sgr_parameters};
 use crate::background_colors::BackgroundColors;
 use crate::foreground_colors::ForegroundColors;
+use crate::names::ParseNameError;
+use crate::style::Style;
 use std::fmt;
 use std::ops::Add;
+use std::str::FromStr;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FontMode {
     Bold(&'static str),
     Dim(&'static str),
@@ -91,6 +96,104 @@ impl FontMode {
     pub fn reset_strikethrough() -> Self {
         FontMode::ResetStrikethrough("\x1B[29m")
     }
+
+    /// Creates a font mode from its name, like `"bold"` or `"reset_underline"`.
+    /// The short forms `"blink"`, `"hidden"` and `"strike"` are also accepted
+    pub fn from_name(name: &str) -> Result<Self, ParseNameError> {
+        let font_mode = match name.to_lowercase().as_str() {
+            "bold" => Self::bold(),
+            "dim" => Self::dim(),
+            "italic" => Self::italic(),
+            "underline" => Self::underline(),
+            "blinking" | "blink" => Self::blinking(),
+            "reverse" => Self::reverse(),
+            "invisible" | "hidden" => Self::invisible(),
+            "strikethrough" | "strike" => Self::strikethrough(),
+            "reset_all" => Self::reset_all(),
+            "reset_bold" => Self::reset_bold(),
+            "reset_dim" => Self::reset_dim(),
+            "reset_italic" => Self::reset_italic(),
+            "reset_underline" => Self::reset_underline(),
+            "reset_blinking" => Self::reset_blinking(),
+            "reset_reverse" => Self::reset_reverse(),
+            "reset_invisible" => Self::reset_invisible(),
+            "reset_strikethrough" => Self::reset_strikethrough(),
+            _ => return Err(ParseNameError::new(name)),
+        };
+        Ok(font_mode)
+    }
+
+    /// Gets the name of the font mode, which `from_name` parses back to the same font mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            FontMode::Bold(_) => "bold",
+            FontMode::Dim(_) => "dim",
+            FontMode::Italic(_) => "italic",
+            FontMode::Underline(_) => "underline",
+            FontMode::Blinking(_) => "blinking",
+            FontMode::Reverse(_) => "reverse",
+            FontMode::Invisible(_) => "invisible",
+            FontMode::Strikethrough(_) => "strikethrough",
+            FontMode::ResetAll(_) => "reset_all",
+            FontMode::ResetBold(_) => "reset_bold",
+            FontMode::ResetDim(_) => "reset_dim",
+            FontMode::ResetItalic(_) => "reset_italic",
+            FontMode::ResetUnderline(_) => "reset_underline",
+            FontMode::ResetBlinking(_) => "reset_blinking",
+            FontMode::ResetReverse(_) => "reset_reverse",
+            FontMode::ResetInvisible(_) => "reset_invisible",
+            FontMode::ResetStrikethrough(_) => "reset_strikethrough",
+        }
+    }
+
+    /// Parses the font mode at the start of a list of SGR parameters,
+    /// returns the font mode and the number of parameters it used
+    pub(crate) fn from_parameters(parameters: &[u8]) -> Option<(Self, usize)> {
+        let font_mode = match parameters.first()? {
+            0 => Self::reset_all(),
+            1 => Self::bold(),
+            2 => Self::dim(),
+            3 => Self::italic(),
+            4 => Self::underline(),
+            5 => Self::blinking(),
+            7 => Self::reverse(),
+            8 => Self::invisible(),
+            9 => Self::strikethrough(),
+            // Bold and dim share their reset code
+            22 => Self::reset_bold(),
+            23 => Self::reset_italic(),
+            24 => Self::reset_underline(),
+            25 => Self::reset_blinking(),
+            27 => Self::reset_reverse(),
+            28 => Self::reset_invisible(),
+            29 => Self::reset_strikethrough(),
+            _ => return None,
+        };
+        Some((font_mode, 1))
+    }
+
+    /// Gets the SGR parameter of the font mode, without the escape sequence around it
+    pub fn parameters(&self) -> String {
+        let sequence = self.to_string();
+        sequence
+            .trim_start_matches("\x1B[")
+            .trim_end_matches('m')
+            .to_string()
+    }
+}
+
+impl FromStr for FontMode {
+    type Err = ParseSgrError;
+
+    /// Parses a single SGR sequence like `"\x1B[1m"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parameters = sgr_parameters(s)?;
+
+        match Self::from_parameters(&parameters) {
+            Some((font_mode, length)) if length == parameters.len() => Ok(font_mode),
+            _ => Err(ParseSgrError::new(s)),
+        }
+    }
 }
 
 impl fmt::Display for FontMode {
@@ -117,58 +220,35 @@ impl fmt::Display for FontMode {
     }
 }
 
-impl Add<BackgroundColors> for FontMode {
-    type Output = String;
-
-    fn add(self, rhs: BackgroundColors) -> Self::Output {
-        let font_mode = self.to_string().replace("\x1B[", "").replace("m", "");
-        let background_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", font_mode, background_color)
-    }
-}
-
 impl Add<ForegroundColors> for FontMode {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: ForegroundColors) -> Self::Output {
-        let font_mode = self.to_string().replace("\x1B[", "").replace("m", "");
-        let foreground_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", font_mode, foreground_color)
+        Style::from(self) + rhs
     }
 }
 
-impl Add<FontMode> for FontMode {
-    type Output = String;
-
-    fn add(self, rhs: FontMode) -> Self::Output {
-        let font_mode_lhs = self.to_string().replace("\x1B[", "").replace("m", "");
-        let font_mode_rhs = rhs.to_string().replace("\x1B[", "").replace("m", "");
+impl Add<BackgroundColors> for FontMode {
+    type Output = Style;
 
-        format!("\x1B[{};{}m", font_mode_lhs, font_mode_rhs)
+    fn add(self, rhs: BackgroundColors) -> Self::Output {
+        Style::from(self) + rhs
     }
 }
 
-impl Add<String> for FontMode {
-    type Output = String;
-
-    fn add(self, rhs: String) -> Self::Output {
-        let font_mode = self.to_string().replace("\x1B[", "").replace("m", "");
-        let string = rhs.to_string().replace("\x1B[", "").replace("m", "");
+impl Add<FontMode> for FontMode {
+    type Output = Style;
 
-        format!("\x1B[{};{}m", font_mode, string)
+    fn add(self, rhs: FontMode) -> Self::Output {
+        Style::from(self) + rhs
     }
 }
 
-impl Add<FontMode> for String {
-    type Output = String;
-
-    fn add(self, rhs: FontMode) -> Self::Output {
-        let string = self.to_string().replace("\x1B[", "").replace("m", "");
-        let font_mode = rhs.to_string().replace("\x1B[", "").replace("m", "");
+impl Add<Style> for FontMode {
+    type Output = Style;
 
-        format!("\x1B[{};{}m", string, font_mode)
+    fn add(self, rhs: Style) -> Self::Output {
+        Style::from(self) + rhs
     }
 }
 
@@ -266,7 +346,7 @@ mod tests {
         let font_mode = FontMode::blinking();
         let foreground_color = ForegroundColors::blue();
 
-        assert_eq!("\x1B[5;34m", font_mode + foreground_color);
+        assert_eq!("\x1B[34;5m", (font_mode + foreground_color).to_string());
     }
 
     #[test]
@@ -274,7 +354,7 @@ mod tests {
         let font_mode = FontMode::blinking();
         let background_color = BackgroundColors::blue();
 
-        assert_eq!("\x1B[5;44m", font_mode + background_color);
+        assert_eq!("\x1B[44;5m", (font_mode + background_color).to_string());
     }
 
     #[test]
@@ -282,22 +362,79 @@ mod tests {
         let font_mode_one = FontMode::blinking();
         let font_mode_two = FontMode::bold();
 
-        assert_eq!("\x1B[5;1m", font_mode_one + font_mode_two);
+        assert_eq!("\x1B[5;1m", (font_mode_one + font_mode_two).to_string());
     }
 
     #[test]
-    fn test_font_mode_add_string() {
+    fn test_font_mode_add_style() {
         let font_mode = FontMode::underline();
-        let string = BackgroundColors::yellow() + ForegroundColors::bright_magenta();
+        let style = BackgroundColors::yellow() + ForegroundColors::bright_magenta();
 
-        assert_eq!("\x1B[4;43;95m", font_mode + string);
+        assert_eq!("\x1B[95;43;4m", (font_mode + style).to_string());
     }
 
     #[test]
-    fn test_string_add_font_mode() {
-        let string = BackgroundColors::yellow() + ForegroundColors::bright_magenta();
-        let font_mode = FontMode::underline();
+    fn test_style_add_font_mode() {
+        let style = BackgroundColors::yellow() + FontMode::underline();
+        let font_mode = FontMode::reset_underline();
+
+        assert_eq!("\x1B[43m", (style + font_mode).to_string());
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!("1", FontMode::bold().parameters());
+        assert_eq!("29", FontMode::reset_strikethrough().parameters());
+    }
 
-        assert_eq!("\x1B[43;95;4m", string + font_mode);
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(FontMode::bold()), "\x1B[1m".parse());
+        assert_eq!(Ok(FontMode::strikethrough()), "\x1B[9m".parse());
+        assert_eq!(Ok(FontMode::reset_all()), "\x1B[m".parse());
+        assert_eq!(Ok(FontMode::reset_bold()), "\x1B[22m".parse());
+        assert_eq!(Ok(FontMode::reset_invisible()), "\x1B[28m".parse());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!("\x1B[6m".parse::<FontMode>().is_err());
+        assert!("\x1B[1;3m".parse::<FontMode>().is_err());
+        assert!("\x1B[31m".parse::<FontMode>().is_err());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Ok(FontMode::bold()), FontMode::from_name("bold"));
+        assert_eq!(Ok(FontMode::blinking()), FontMode::from_name("blink"));
+        assert_eq!(Ok(FontMode::strikethrough()), FontMode::from_name("Strike"));
+        assert!(FontMode::from_name("bolder").is_err());
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        let font_modes = [
+            FontMode::bold(),
+            FontMode::dim(),
+            FontMode::italic(),
+            FontMode::underline(),
+            FontMode::blinking(),
+            FontMode::reverse(),
+            FontMode::invisible(),
+            FontMode::strikethrough(),
+            FontMode::reset_all(),
+            FontMode::reset_bold(),
+            FontMode::reset_dim(),
+            FontMode::reset_italic(),
+            FontMode::reset_underline(),
+            FontMode::reset_blinking(),
+            FontMode::reset_reverse(),
+            FontMode::reset_invisible(),
+            FontMode::reset_strikethrough(),
+        ];
+
+        for font_mode in font_modes {
+            assert_eq!(Ok(font_mode.clone()), FontMode::from_name(font_mode.name()));
+        }
     }
 }
@@ -1,8 +1,17 @@
+use crate::ansi::{ParseSgrError, sgr_parameters};
 use crate::background_colors::BackgroundColors;
+use crate::color_conversion::{
+    ansi256_to_rgb, hsl_to_rgb, parse_hex, rgb_to_ansi16, rgb_to_ansi256,
+};
+use crate::color_support::ColorSupport;
 use crate::font_mode::FontMode;
+use crate::names::{ColorName, ParseNameError};
+use crate::style::Style;
 use std::fmt;
 use std::ops::Add;
+use std::str::FromStr;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ForegroundColors {
     Black(&'static str),
     Red(&'static str),
@@ -21,6 +30,8 @@ pub enum ForegroundColors {
     BrightMagenta(&'static str),
     BrightCyan(&'static str),
     BrightWhite(&'static str),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
 }
 
 impl ForegroundColors {
@@ -56,6 +67,7 @@ impl ForegroundColors {
         ForegroundColors::White("\x1B[37m")
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         ForegroundColors::Default("\x1B[39m")
     }
@@ -91,6 +103,172 @@ impl ForegroundColors {
     pub fn bright_white() -> Self {
         ForegroundColors::BrightWhite("\x1B[97m")
     }
+
+    /// Creates a color from the 256-color palette
+    pub fn indexed(index: u8) -> Self {
+        ForegroundColors::Indexed(index)
+    }
+
+    /// Creates a 24-bit truecolor
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        ForegroundColors::Rgb(red, green, blue)
+    }
+
+    /// Creates a truecolor from a hex string like `"#ff8800"` or `"#f80"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::foreground_colors::ForegroundColors;
+    ///
+    /// let orange = ForegroundColors::from_hex("#ff8800").unwrap();
+    ///
+    /// assert_eq!("\x1B[38;2;255;136;0m", orange.to_string());
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let (red, green, blue) = parse_hex(hex)?;
+        Some(ForegroundColors::Rgb(red, green, blue))
+    }
+
+    /// Creates a truecolor from a hue in degrees and a saturation and lightness between 0 and 1
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let (red, green, blue) = hsl_to_rgb(hue, saturation, lightness);
+        ForegroundColors::Rgb(red, green, blue)
+    }
+
+    /// Creates a color from its name, like `"bright_red"`, `"default"`, `"indexed(208)"` or `"#ff8800"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::foreground_colors::ForegroundColors;
+    ///
+    /// assert_eq!(Ok(ForegroundColors::bright_red()), ForegroundColors::from_name("bright_red"));
+    /// assert_eq!(Ok(ForegroundColors::indexed(208)), ForegroundColors::from_name("indexed(208)"));
+    /// ```
+    pub fn from_name(name: &str) -> Result<Self, ParseNameError> {
+        match ColorName::parse(name) {
+            Some(ColorName::Default) => Ok(Self::default()),
+            Some(ColorName::Ansi16(index)) => Ok(Self::from_ansi16(index)),
+            Some(ColorName::Indexed(index)) => Ok(ForegroundColors::Indexed(index)),
+            Some(ColorName::Rgb(red, green, blue)) => Ok(ForegroundColors::Rgb(red, green, blue)),
+            None => Err(ParseNameError::new(name)),
+        }
+    }
+
+    /// Gets the name of the color, which `from_name` parses back to the same color
+    pub fn name(&self) -> String {
+        let color = match self {
+            ForegroundColors::Indexed(index) => ColorName::Indexed(*index),
+            ForegroundColors::Rgb(red, green, blue) => ColorName::Rgb(*red, *green, *blue),
+            _ => match self.parameters().parse::<u8>().unwrap_or(39) {
+                code @ 30..=37 => ColorName::Ansi16(code - 30),
+                code @ 90..=97 => ColorName::Ansi16(code - 90 + 8),
+                _ => ColorName::Default,
+            },
+        };
+        color.to_string()
+    }
+
+    /// Creates one of the 16 basic colors, 0 to 7 are the normal and 8 to 15 the bright colors
+    fn from_ansi16(index: u8) -> Self {
+        match index {
+            0 => Self::black(),
+            1 => Self::red(),
+            2 => Self::green(),
+            3 => Self::yellow(),
+            4 => Self::blue(),
+            5 => Self::magenta(),
+            6 => Self::cyan(),
+            7 => Self::white(),
+            8 => Self::bright_black(),
+            9 => Self::bright_red(),
+            10 => Self::bright_green(),
+            11 => Self::bright_yellow(),
+            12 => Self::bright_blue(),
+            13 => Self::bright_magenta(),
+            14 => Self::bright_cyan(),
+            _ => Self::bright_white(),
+        }
+    }
+
+    /// Parses the color at the start of a list of SGR parameters,
+    /// returns the color and the number of parameters it used
+    pub(crate) fn from_parameters(parameters: &[u8]) -> Option<(Self, usize)> {
+        match *parameters {
+            [code @ 30..=37, ..] => Some((Self::from_ansi16(code - 30), 1)),
+            [39, ..] => Some((Self::default(), 1)),
+            [code @ 90..=97, ..] => Some((Self::from_ansi16(code - 90 + 8), 1)),
+            [38, 5, index, ..] => Some((ForegroundColors::Indexed(index), 3)),
+            [38, 2, red, green, blue, ..] => Some((ForegroundColors::Rgb(red, green, blue), 5)),
+            _ => None,
+        }
+    }
+
+    /// Converts the color to the nearest color the terminal supports,
+    /// returns None if the terminal does not support colors
+    pub fn downsample(self, support: ColorSupport) -> Option<Self> {
+        match (self, support) {
+            (_, ColorSupport::None) => None,
+            (ForegroundColors::Rgb(red, green, blue), ColorSupport::Ansi256) => Some(
+                ForegroundColors::Indexed(rgb_to_ansi256((red, green, blue))),
+            ),
+            (ForegroundColors::Rgb(red, green, blue), ColorSupport::Basic) => {
+                Some(Self::from_ansi16(rgb_to_ansi16((red, green, blue))))
+            }
+            (ForegroundColors::Indexed(index), ColorSupport::Basic) => {
+                Some(Self::from_ansi16(rgb_to_ansi16(ansi256_to_rgb(index))))
+            }
+            (color, _) => Some(color),
+        }
+    }
+
+    /// Gets the RGB value of the color, basic colors use the default xterm palette.
+    /// Returns None for the default color, as that depends on the terminal
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            ForegroundColors::Indexed(index) => Some(ansi256_to_rgb(*index)),
+            ForegroundColors::Rgb(red, green, blue) => Some((*red, *green, *blue)),
+            ForegroundColors::Default(_) => None,
+            _ => {
+                let code: u8 = self.parameters().parse().ok()?;
+                match code {
+                    30..=37 => Some(ansi256_to_rgb(code - 30)),
+                    90..=97 => Some(ansi256_to_rgb(code - 90 + 8)),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Gets the SGR parameters of the color, without the escape sequence around them
+    pub fn parameters(&self) -> String {
+        match self {
+            ForegroundColors::Indexed(index) => format!("38;5;{}", index),
+            ForegroundColors::Rgb(red, green, blue) => format!("38;2;{};{};{}", red, green, blue),
+            _ => {
+                let sequence = self.to_string();
+                sequence
+                    .trim_start_matches("\x1B[")
+                    .trim_end_matches('m')
+                    .to_string()
+            }
+        }
+    }
+}
+
+impl FromStr for ForegroundColors {
+    type Err = ParseSgrError;
+
+    /// Parses a single SGR sequence like `"\x1B[31m"` or `"\x1B[38;5;208m"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parameters = sgr_parameters(s)?;
+
+        match Self::from_parameters(&parameters) {
+            Some((color, length)) if length == parameters.len() => Ok(color),
+            _ => Err(ParseSgrError::new(s)),
+        }
+    }
 }
 
 impl fmt::Display for ForegroundColors {
@@ -113,62 +291,43 @@ impl fmt::Display for ForegroundColors {
             ForegroundColors::BrightMagenta(s) => write!(f, "{}", s),
             ForegroundColors::BrightCyan(s) => write!(f, "{}", s),
             ForegroundColors::BrightWhite(s) => write!(f, "{}", s),
+            ForegroundColors::Indexed(index) => write!(f, "\x1B[38;5;{}m", index),
+            ForegroundColors::Rgb(red, green, blue) => {
+                write!(f, "\x1B[38;2;{};{};{}m", red, green, blue)
+            }
         }
     }
 }
 
 impl Add<BackgroundColors> for ForegroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: BackgroundColors) -> Self::Output {
-        let foreground_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let background_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", foreground_color, background_color)
+        Style::from(self) + rhs
     }
 }
 
 impl Add<FontMode> for ForegroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: FontMode) -> Self::Output {
-        let foreground_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let font_mode = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", foreground_color, font_mode)
+        Style::from(self) + rhs
     }
 }
 
 impl Add<ForegroundColors> for ForegroundColors {
-    type Output = String;
+    type Output = Style;
 
     fn add(self, rhs: ForegroundColors) -> Self::Output {
-        let foreground_color_lhs = self.to_string().replace("\x1B[", "").replace("m", "");
-        let foreground_color_rhs = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", foreground_color_lhs, foreground_color_rhs)
-    }
-}
-
-impl Add<String> for ForegroundColors {
-    type Output = String;
-
-    fn add(self, rhs: String) -> Self::Output {
-        let foreground_color = self.to_string().replace("\x1B[", "").replace("m", "");
-        let string = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", foreground_color, string)
+        Style::from(self) + rhs
     }
 }
 
-impl Add<ForegroundColors> for String {
-    type Output = String;
+impl Add<Style> for ForegroundColors {
+    type Output = Style;
 
-    fn add(self, rhs: ForegroundColors) -> Self::Output {
-        let string = self.to_string().replace("\x1B[", "").replace("m", "");
-        let foreground_color = rhs.to_string().replace("\x1B[", "").replace("m", "");
-
-        format!("\x1B[{};{}m", string, foreground_color)
+    fn add(self, rhs: Style) -> Self::Output {
+        Style::from(self) + rhs
     }
 }
 
@@ -261,12 +420,88 @@ mod tests {
         assert_eq!("\x1B[97m", ForegroundColors::bright_white().to_string());
     }
 
+    #[test]
+    fn test_indexed() {
+        assert_eq!("\x1B[38;5;208m", ForegroundColors::indexed(208).to_string());
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(
+            "\x1B[38;2;1;2;3m",
+            ForegroundColors::rgb(1, 2, 3).to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(
+            "\x1B[38;2;255;136;0m",
+            ForegroundColors::from_hex("#ff8800").unwrap().to_string()
+        );
+        assert!(ForegroundColors::from_hex("orange").is_none());
+    }
+
+    #[test]
+    fn test_from_hsl() {
+        assert_eq!(
+            "\x1B[38;2;0;0;255m",
+            ForegroundColors::from_hsl(240.0, 1.0, 0.5).to_string()
+        );
+    }
+
+    #[test]
+    fn test_downsample_truecolor() {
+        let color = ForegroundColors::rgb(255, 136, 0).downsample(ColorSupport::TrueColor);
+
+        assert_eq!("\x1B[38;2;255;136;0m", color.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_ansi256() {
+        let color = ForegroundColors::rgb(255, 136, 0).downsample(ColorSupport::Ansi256);
+        let indexed = ForegroundColors::indexed(17).downsample(ColorSupport::Ansi256);
+
+        assert_eq!("\x1B[38;5;208m", color.unwrap().to_string());
+        assert_eq!("\x1B[38;5;17m", indexed.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_basic() {
+        let color = ForegroundColors::rgb(255, 136, 0).downsample(ColorSupport::Basic);
+        let indexed = ForegroundColors::indexed(196).downsample(ColorSupport::Basic);
+        let basic = ForegroundColors::indexed(4).downsample(ColorSupport::Basic);
+        let unchanged = ForegroundColors::cyan().downsample(ColorSupport::Basic);
+
+        assert_eq!("\x1B[33m", color.unwrap().to_string());
+        assert_eq!("\x1B[91m", indexed.unwrap().to_string());
+        assert_eq!("\x1B[34m", basic.unwrap().to_string());
+        assert_eq!("\x1B[36m", unchanged.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_downsample_none() {
+        assert!(
+            ForegroundColors::rgb(255, 136, 0)
+                .downsample(ColorSupport::None)
+                .is_none()
+        );
+        assert!(
+            ForegroundColors::red()
+                .downsample(ColorSupport::None)
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_foreground_color_add_foreground_color() {
         let foreground_color_one = ForegroundColors::cyan();
         let foreground_color_two = ForegroundColors::magenta();
 
-        assert_eq!("\x1B[36;35m", foreground_color_one + foreground_color_two);
+        assert_eq!(
+            "\x1B[35m",
+            (foreground_color_one + foreground_color_two).to_string()
+        );
     }
 
     #[test]
@@ -274,7 +509,10 @@ mod tests {
         let foreground_color = ForegroundColors::cyan();
         let background_color = BackgroundColors::magenta();
 
-        assert_eq!("\x1B[36;45m", foreground_color + background_color);
+        assert_eq!(
+            "\x1B[36;45m",
+            (foreground_color + background_color).to_string()
+        );
     }
 
     #[test]
@@ -282,22 +520,116 @@ mod tests {
         let foreground_color = ForegroundColors::cyan();
         let font_mode = FontMode::underline();
 
-        assert_eq!("\x1B[36;4m", foreground_color + font_mode);
+        assert_eq!("\x1B[36;4m", (foreground_color + font_mode).to_string());
     }
 
     #[test]
-    fn test_foreground_color_add_string() {
+    fn test_foreground_color_add_style() {
         let foreground_color = ForegroundColors::cyan();
-        let string = BackgroundColors::black() + FontMode::strikethrough();
+        let style = BackgroundColors::black() + FontMode::strikethrough();
 
-        assert_eq!("\x1B[36;40;9m", foreground_color + string);
+        assert_eq!("\x1B[36;40;9m", (foreground_color + style).to_string());
     }
 
     #[test]
-    fn test_string_add_foreground_color() {
-        let string = BackgroundColors::black() + FontMode::strikethrough();
+    fn test_style_add_foreground_color() {
+        let style = ForegroundColors::red() + BackgroundColors::black();
         let foreground_color = ForegroundColors::cyan();
 
-        assert_eq!("\x1B[40;9;36m", string + foreground_color);
+        assert_eq!("\x1B[36;40m", (style + foreground_color).to_string());
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!("31", ForegroundColors::red().parameters());
+        assert_eq!("38;5;208", ForegroundColors::indexed(208).parameters());
+        assert_eq!("38;2;1;2;3", ForegroundColors::rgb(1, 2, 3).parameters());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(ForegroundColors::red()), "\x1B[31m".parse());
+        assert_eq!(Ok(ForegroundColors::default()), "\x1B[39m".parse());
+        assert_eq!(Ok(ForegroundColors::bright_cyan()), "\x1B[96m".parse());
+        assert_eq!(Ok(ForegroundColors::indexed(208)), "\x1B[38;5;208m".parse());
+        assert_eq!(
+            Ok(ForegroundColors::rgb(1, 2, 3)),
+            "\x1B[38;2;1;2;3m".parse()
+        );
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        for index in 0..16 {
+            let color = ForegroundColors::from_ansi16(index);
+            assert_eq!(Ok(color.clone()), color.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!("\x1B[31;1m".parse::<ForegroundColors>().is_err());
+        assert!("\x1B[38;5m".parse::<ForegroundColors>().is_err());
+        assert!("\x1B[48;5;208m".parse::<ForegroundColors>().is_err());
+        assert!("31".parse::<ForegroundColors>().is_err());
+    }
+
+    #[test]
+    fn test_to_rgb() {
+        assert_eq!(Some((205, 0, 0)), ForegroundColors::red().to_rgb());
+        assert_eq!(
+            Some((92, 92, 255)),
+            ForegroundColors::bright_blue().to_rgb()
+        );
+        assert_eq!(Some((255, 135, 0)), ForegroundColors::indexed(208).to_rgb());
+        assert_eq!(Some((1, 2, 3)), ForegroundColors::rgb(1, 2, 3).to_rgb());
+        assert_eq!(None, ForegroundColors::default().to_rgb());
+    }
+
+    #[test]
+    fn test_to_rgb_of_unknown_code() {
+        assert_eq!(None, ForegroundColors::Black("\x1B[40m").to_rgb());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Ok(ForegroundColors::black()),
+            ForegroundColors::from_name("black")
+        );
+        assert_eq!(
+            Ok(ForegroundColors::default()),
+            ForegroundColors::from_name("default")
+        );
+        assert_eq!(
+            Ok(ForegroundColors::bright_magenta()),
+            ForegroundColors::from_name("BRIGHT_MAGENTA")
+        );
+        assert_eq!(
+            Ok(ForegroundColors::rgb(255, 136, 0)),
+            ForegroundColors::from_name("#ff8800")
+        );
+        assert!(ForegroundColors::from_name("orange").is_err());
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        let colors = [
+            ForegroundColors::black(),
+            ForegroundColors::white(),
+            ForegroundColors::default(),
+            ForegroundColors::bright_black(),
+            ForegroundColors::bright_white(),
+            ForegroundColors::indexed(208),
+            ForegroundColors::rgb(1, 2, 3),
+        ];
+
+        for color in colors {
+            assert_eq!(
+                Ok(color.clone()),
+                ForegroundColors::from_name(&color.name())
+            );
+        }
+        assert_eq!("bright_yellow", ForegroundColors::bright_yellow().name());
     }
 }
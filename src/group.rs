@@ -0,0 +1,110 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+thread_local! {
+    /// Number of groups and spans that are open on the current thread
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Indents all records of the current thread until it is dropped, created with [`crate::Apollo::group`]
+///
+/// Groups can not be sent to another thread, as the indentation belongs to the thread
+#[must_use = "the records are no longer indented as soon as the guard is dropped"]
+pub struct Group {
+    /// Depth to restore when the group ends, `None` if the group was not entered
+    depth: Option<usize>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Group {
+    /// Indents the following records one level deeper
+    pub(crate) fn enter() -> Group {
+        let depth = depth();
+        set_depth(depth + 1);
+
+        Group {
+            depth: Some(depth),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Leaves the indentation as it is, for a group whose title was not logged
+    pub(crate) fn skip() -> Group {
+        Group {
+            depth: None,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        if let Some(depth) = self.depth {
+            set_depth(depth);
+        }
+    }
+}
+
+/// Gets the number of groups the current thread is in
+pub fn depth() -> usize {
+    DEPTH.get()
+}
+
+/// Sets the number of groups the current thread is in
+pub(crate) fn set_depth(depth: usize) {
+    DEPTH.set(depth);
+}
+
+/// Gets the tree-drawing characters in front of a message at the given depth
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::group::tree;
+///
+/// assert_eq!("", tree(0));
+/// assert_eq!("├─ ", tree(1));
+/// assert_eq!("│  │  ├─ ", tree(3));
+/// ```
+pub fn tree(depth: usize) -> String {
+    match depth {
+        0 => String::new(),
+        depth => format!("{}├─ ", "│  ".repeat(depth - 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_and_drop() {
+        let outer = Group::enter();
+        {
+            let _inner = Group::enter();
+            assert_eq!(2, depth());
+        }
+        assert_eq!(1, depth());
+
+        drop(outer);
+        assert_eq!(0, depth());
+    }
+
+    #[test]
+    fn test_skip() {
+        let outer = Group::enter();
+        drop(Group::skip());
+        assert_eq!(1, depth());
+
+        drop(outer);
+        assert_eq!(0, depth());
+    }
+
+    #[test]
+    fn test_threads_have_their_own_depth() {
+        let _group = Group::enter();
+
+        assert_eq!(0, std::thread::spawn(depth).join().unwrap());
+        assert_eq!(1, depth());
+    }
+}
@@ -0,0 +1,402 @@
+use crate::ansi::{Segment, segments, sgr_parameters};
+use crate::font_mode::FontMode;
+use crate::record::Record;
+use crate::sink::Sink;
+use crate::style::Style;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Start of the page written by the HTML sink, up to and including the opening of the body
+const HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Apollo log</title>
+<style>
+:root { --apollo-foreground: #e5e5e5; --apollo-background: #1e1e1e; }
+body { margin: 0; padding: 1em; color: var(--apollo-foreground); background: var(--apollo-background); }
+.log { font-family: monospace; white-space: pre-wrap; }
+@keyframes apollo-blink { 50% { opacity: 0; } }
+</style>
+</head>
+<body>
+<div class="log">
+"#;
+
+/// End of the page written by the HTML sink
+const FOOTER: &str = "</div>\n</body>\n</html>\n";
+
+/// Escapes the characters that have a special meaning in HTML
+fn escape_html(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Gets the inline CSS of a style
+fn css(style: &Style) -> String {
+    let hex = |(red, green, blue): (u8, u8, u8)| format!("#{red:02x}{green:02x}{blue:02x}");
+
+    let mut fg = style.fg.as_ref().and_then(|fg| fg.to_rgb()).map(hex);
+    let mut bg = style.bg.as_ref().and_then(|bg| bg.to_rgb()).map(hex);
+
+    // Reverse swaps the colors, including the default colors of the page
+    if style.modes.contains(&FontMode::reverse()) {
+        (fg, bg) = (
+            Some(bg.unwrap_or(String::from("var(--apollo-background, #1e1e1e)"))),
+            Some(fg.unwrap_or(String::from("var(--apollo-foreground, #e5e5e5)"))),
+        );
+    }
+
+    let mut declarations: Vec<String> = Vec::new();
+    let mut decorations: Vec<&str> = Vec::new();
+
+    if let Some(fg) = fg {
+        declarations.push(format!("color:{fg}"));
+    }
+    if let Some(bg) = bg {
+        declarations.push(format!("background-color:{bg}"));
+    }
+
+    for mode in &style.modes {
+        match mode {
+            FontMode::Bold(_) => declarations.push(String::from("font-weight:bold")),
+            FontMode::Dim(_) => declarations.push(String::from("opacity:0.6")),
+            FontMode::Italic(_) => declarations.push(String::from("font-style:italic")),
+            FontMode::Underline(_) => decorations.push("underline"),
+            FontMode::Strikethrough(_) => decorations.push("line-through"),
+            FontMode::Blinking(_) => {
+                declarations.push(String::from("animation:apollo-blink 1s step-end infinite"))
+            }
+            FontMode::Invisible(_) => declarations.push(String::from("visibility:hidden")),
+            // Reverse is handled above and styles never contain resets
+            _ => {}
+        }
+    }
+
+    if !decorations.is_empty() {
+        declarations.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+
+    declarations.join(";")
+}
+
+/// Converts a line with ANSI escape sequences to HTML, colors and font modes become
+/// `<span>` elements with inline styles and all other escape sequences are removed
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::html::ansi_to_html;
+///
+/// assert_eq!(
+///     r#"<span style="color:#cd0000;font-weight:bold">error</span> &lt;here&gt;"#,
+///     ansi_to_html("\x1B[31;1merror\x1B[0m <here>")
+/// );
+/// ```
+pub fn ansi_to_html(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut style = Style::new();
+
+    for segment in segments(s) {
+        match segment {
+            Segment::Escape(sequence) => {
+                if let Ok(parameters) = sgr_parameters(sequence) {
+                    style = style.apply_parameters(&parameters);
+                }
+            }
+            Segment::Text(text) => {
+                let css = css(&style);
+                if css.is_empty() {
+                    result.push_str(&escape_html(text));
+                } else {
+                    result.push_str(&format!(
+                        "<span style=\"{css}\">{}</span>",
+                        escape_html(text)
+                    ));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Sink that writes records as a self-contained, styled HTML page
+///
+/// The end of the page is written when the sink is dropped,
+/// browsers also show the page while it is still being written
+///
+/// # Examples
+///
+/// ```no_run
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::html::HtmlSink;
+/// use std::sync::Arc;
+///
+/// let l = Apollo {
+///     sinks: vec![Arc::new(HtmlSink::create("log.html").unwrap())],
+///     ..Apollo::new()
+/// };
+///
+/// l.info("This message is also written to log.html");
+/// ```
+pub struct HtmlSink<W: Write + Send = File> {
+    writer: Mutex<W>,
+}
+
+impl HtmlSink<File> {
+    /// Creates the HTML file, overwriting it if it already exists
+    pub fn create(path: impl AsRef<Path>) -> io::Result<HtmlSink<File>> {
+        HtmlSink::new(File::create(path)?)
+    }
+}
+
+impl<W: Write + Send> HtmlSink<W> {
+    /// Creates a sink that writes to the given writer, the start of the page is written immediately
+    pub fn new(mut writer: W) -> io::Result<HtmlSink<W>> {
+        writer.write_all(HEADER.as_bytes())?;
+        writer.flush()?;
+
+        Ok(HtmlSink {
+            writer: Mutex::new(writer),
+        })
+    }
+}
+
+impl<W: Write + Send> Sink for HtmlSink<W> {
+    fn log(&self, record: &Record) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+
+        writeln!(
+            writer,
+            "<div class=\"{}\">{}</div>",
+            record.level.name().to_lowercase(),
+            ansi_to_html(&record.line)
+        )?;
+        writer.flush()
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+impl<W: Write + Send> Drop for HtmlSink<W> {
+    fn drop(&mut self) {
+        if let Ok(writer) = self.writer.get_mut() {
+            // Errors can not be reported from drop, the page is still readable without its end
+            let _ = writer.write_all(FOOTER.as_bytes());
+            let _ = writer.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::background_colors::BackgroundColors;
+    use crate::foreground_colors::ForegroundColors;
+    use crate::levels::Levels;
+    use chrono::Utc;
+    use std::fs;
+
+    fn span(css: &str, text: &str) -> String {
+        format!("<span style=\"{css}\">{text}</span>")
+    }
+
+    #[test]
+    fn test_plain_text_is_escaped() {
+        assert_eq!(
+            "a &amp; b &lt;c&gt; &quot;d&quot; &#39;e&#39;",
+            ansi_to_html("a & b <c> \"d\" 'e'")
+        );
+    }
+
+    #[test]
+    fn test_foreground_colors() {
+        let colors = [
+            (ForegroundColors::black(), "#000000"),
+            (ForegroundColors::red(), "#cd0000"),
+            (ForegroundColors::green(), "#00cd00"),
+            (ForegroundColors::yellow(), "#cdcd00"),
+            (ForegroundColors::blue(), "#0000ee"),
+            (ForegroundColors::magenta(), "#cd00cd"),
+            (ForegroundColors::cyan(), "#00cdcd"),
+            (ForegroundColors::white(), "#e5e5e5"),
+            (ForegroundColors::bright_black(), "#7f7f7f"),
+            (ForegroundColors::bright_red(), "#ff0000"),
+            (ForegroundColors::bright_green(), "#00ff00"),
+            (ForegroundColors::bright_yellow(), "#ffff00"),
+            (ForegroundColors::bright_blue(), "#5c5cff"),
+            (ForegroundColors::bright_magenta(), "#ff00ff"),
+            (ForegroundColors::bright_cyan(), "#00ffff"),
+            (ForegroundColors::bright_white(), "#ffffff"),
+            (ForegroundColors::indexed(208), "#ff8700"),
+            (ForegroundColors::rgb(1, 2, 3), "#010203"),
+        ];
+
+        for (color, hex) in colors {
+            assert_eq!(
+                span(&format!("color:{hex}"), "x"),
+                ansi_to_html(&format!("{color}x"))
+            );
+        }
+        assert_eq!(
+            "x",
+            ansi_to_html(&format!("{}x", ForegroundColors::default()))
+        );
+    }
+
+    #[test]
+    fn test_background_colors() {
+        let colors = [
+            (BackgroundColors::black(), "#000000"),
+            (BackgroundColors::red(), "#cd0000"),
+            (BackgroundColors::green(), "#00cd00"),
+            (BackgroundColors::yellow(), "#cdcd00"),
+            (BackgroundColors::blue(), "#0000ee"),
+            (BackgroundColors::magenta(), "#cd00cd"),
+            (BackgroundColors::cyan(), "#00cdcd"),
+            (BackgroundColors::white(), "#e5e5e5"),
+            (BackgroundColors::bright_black(), "#7f7f7f"),
+            (BackgroundColors::bright_red(), "#ff0000"),
+            (BackgroundColors::bright_green(), "#00ff00"),
+            (BackgroundColors::bright_yellow(), "#ffff00"),
+            (BackgroundColors::bright_blue(), "#5c5cff"),
+            (BackgroundColors::bright_magenta(), "#ff00ff"),
+            (BackgroundColors::bright_cyan(), "#00ffff"),
+            (BackgroundColors::bright_white(), "#ffffff"),
+            (BackgroundColors::indexed(17), "#00005f"),
+            (BackgroundColors::rgb(1, 2, 3), "#010203"),
+        ];
+
+        for (color, hex) in colors {
+            assert_eq!(
+                span(&format!("background-color:{hex}"), "x"),
+                ansi_to_html(&format!("{color}x"))
+            );
+        }
+        assert_eq!(
+            "x",
+            ansi_to_html(&format!("{}x", BackgroundColors::default()))
+        );
+    }
+
+    #[test]
+    fn test_font_modes() {
+        let modes = [
+            (FontMode::bold(), "font-weight:bold"),
+            (FontMode::dim(), "opacity:0.6"),
+            (FontMode::italic(), "font-style:italic"),
+            (FontMode::underline(), "text-decoration:underline"),
+            (
+                FontMode::blinking(),
+                "animation:apollo-blink 1s step-end infinite",
+            ),
+            (
+                FontMode::reverse(),
+                "color:var(--apollo-background, #1e1e1e);background-color:var(--apollo-foreground, #e5e5e5)",
+            ),
+            (FontMode::invisible(), "visibility:hidden"),
+            (FontMode::strikethrough(), "text-decoration:line-through"),
+        ];
+
+        for (mode, css) in modes {
+            assert_eq!(span(css, "x"), ansi_to_html(&format!("{mode}x")));
+        }
+    }
+
+    #[test]
+    fn test_reset_font_modes() {
+        let resets = [
+            (FontMode::bold(), FontMode::reset_bold()),
+            (FontMode::dim(), FontMode::reset_dim()),
+            (FontMode::italic(), FontMode::reset_italic()),
+            (FontMode::underline(), FontMode::reset_underline()),
+            (FontMode::blinking(), FontMode::reset_blinking()),
+            (FontMode::reverse(), FontMode::reset_reverse()),
+            (FontMode::invisible(), FontMode::reset_invisible()),
+            (FontMode::strikethrough(), FontMode::reset_strikethrough()),
+            (FontMode::bold(), FontMode::reset_all()),
+        ];
+
+        for (mode, reset) in resets {
+            assert!(ansi_to_html(&format!("{mode}a{reset}b")).ends_with("</span>b"));
+        }
+    }
+
+    #[test]
+    fn test_combined_styles() {
+        let style = ForegroundColors::bright_white()
+            + BackgroundColors::bright_red()
+            + FontMode::underline()
+            + FontMode::strikethrough();
+
+        assert_eq!(
+            span(
+                "color:#ffffff;background-color:#ff0000;text-decoration:underline line-through",
+                "critical"
+            ),
+            ansi_to_html(&format!("{style}critical\x1B[0m"))
+        );
+    }
+
+    #[test]
+    fn test_reverse_with_colors() {
+        let style = ForegroundColors::red() + FontMode::reverse();
+
+        assert_eq!(
+            span(
+                "color:var(--apollo-background, #1e1e1e);background-color:#cd0000",
+                "x"
+            ),
+            ansi_to_html(&format!("{style}x"))
+        );
+    }
+
+    #[test]
+    fn test_other_escape_sequences_are_removed() {
+        assert_eq!(
+            "main.rs:1",
+            ansi_to_html("\x1B]8;;file:///main.rs\x1B\\main.rs:1\x1B]8;;\x1B\\\x1B[2K")
+        );
+    }
+
+    #[test]
+    fn test_html_sink() {
+        let path = std::env::temp_dir().join(format!("apollo-html-{}.html", std::process::id()));
+        let sink = HtmlSink::create(&path).unwrap();
+
+        sink.log(&Record {
+            location: String::from("main.rs:1"),
+            line: String::from("\x1B[33m[ WARN  ]\x1B[0m careful & <done>"),
+            ..Record::new(Levels::WARN, Utc::now(), "careful")
+        })
+        .unwrap();
+        drop(sink);
+
+        let page = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("@keyframes apollo-blink"));
+        assert!(page.contains(
+            "<div class=\"warn\"><span style=\"color:#cdcd00\">[ WARN  ]</span> careful &amp; &lt;done&gt;</div>\n"
+        ));
+        assert!(page.ends_with("</html>\n"));
+    }
+}
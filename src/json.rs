@@ -0,0 +1,262 @@
+use crate::field::Field;
+use crate::record::Record;
+use crate::sink::Sink;
+use crate::value::Value;
+use chrono::SecondsFormat;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Escapes a string and puts it in quotes
+fn string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Converts a value to JSON, numbers that JSON can not represent become `null`
+fn value(value: &Value) -> String {
+    match value {
+        Value::Bool(value) => value.to_string(),
+        Value::I64(value) => value.to_string(),
+        Value::U64(value) => value.to_string(),
+        Value::F64(value) if value.is_finite() => value.to_string(),
+        Value::F64(_) => String::from("null"),
+        Value::Str(value) => string(value),
+    }
+}
+
+/// Converts fields to a JSON object, a key that occurs more than once keeps its position
+/// and gets its last value, so fields of a record override fields of the context
+fn object(fields: &[Field]) -> String {
+    let mut unique: Vec<&Field> = Vec::with_capacity(fields.len());
+    for field in fields {
+        match unique.iter_mut().find(|other| other.key == field.key) {
+            Some(other) => *other = field,
+            None => unique.push(field),
+        }
+    }
+
+    let members: Vec<String> = unique
+        .iter()
+        .map(|field| format!("{}:{}", string(&field.key), value(&field.value)))
+        .collect();
+
+    format!("{{{}}}", members.join(","))
+}
+
+/// Converts a record to a single line JSON object, the fields are kept in a nested `fields` object
+/// where a repeated key gets its last value.
+/// The logger name is left out for the root logger, the thread and process only when they are known
+/// and the depth only inside groups
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::field::Field;
+/// use crate::apollo_logger::json::to_json;
+/// use crate::apollo_logger::levels::Levels;
+/// use crate::apollo_logger::record::Record;
+/// use chrono::{TimeZone, Utc};
+///
+/// let record = Record {
+///     location: String::from("main.rs:1"),
+///     fields: vec![Field::new("user_id", 42), Field::new("ok", true)],
+///     ..Record::new(Levels::INFO, Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(), "login")
+/// };
+///
+/// assert_eq!(
+///     r#"{"time":"2025-01-02T03:04:05.000Z","level":"INFO","location":"main.rs:1","message":"login","fields":{"user_id":42,"ok":true}}"#,
+///     to_json(&record)
+/// );
+/// ```
+pub fn to_json(record: &Record) -> String {
+    let mut optional = String::new();
+    if !record.logger.is_empty() {
+        optional.push_str(&format!("\"logger\":{},", string(&record.logger)));
+    }
+    if let Some(thread_name) = &record.thread_name {
+        optional.push_str(&format!("\"thread\":{},", string(thread_name)));
+    }
+    if let Some(thread_id) = record.thread_id {
+        optional.push_str(&format!("\"thread_id\":{thread_id},"));
+    }
+    if let Some(process_id) = record.process_id {
+        optional.push_str(&format!("\"pid\":{process_id},"));
+    }
+    if record.depth > 0 {
+        optional.push_str(&format!("\"depth\":{},", record.depth));
+    }
+
+    format!(
+        "{{\"time\":{},\"level\":{},{optional}\"location\":{},\"message\":{},\"fields\":{}}}",
+        string(&record.time.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        string(record.level.name()),
+        string(&record.location),
+        string(&record.message),
+        object(&record.fields)
+    )
+}
+
+/// Sink that writes every record as a line of JSON
+pub struct JsonSink<W: Write + Send = File> {
+    writer: Mutex<W>,
+}
+
+impl JsonSink<File> {
+    /// Opens a file for appending, it is created if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<JsonSink<File>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(JsonSink::new(file))
+    }
+}
+
+impl<W: Write + Send> JsonSink<W> {
+    pub fn new(writer: W) -> JsonSink<W> {
+        JsonSink {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<W: Write + Send> Sink for JsonSink<W> {
+    fn log(&self, record: &Record) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+
+        writeln!(writer, "{}", to_json(record))?;
+        writer.flush()
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::Levels;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+
+    fn record(fields: Vec<Field>) -> Record {
+        let time = Utc.with_ymd_and_hms(2025, 6, 7, 8, 9, 10).unwrap();
+
+        Record {
+            location: String::from("db.rs:88"),
+            fields,
+            ..Record::new(Levels::WARN, time, "slow query")
+        }
+    }
+
+    #[test]
+    fn test_string() {
+        assert_eq!(
+            r#""a \"b\" \\ \n\r\t \u001b é""#,
+            string("a \"b\" \\ \n\r\t \x1B é")
+        );
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(
+            r#"{"a":true,"b":-1,"c":18446744073709551615,"d":0.5,"e":null,"f":"x"}"#,
+            object(&[
+                Field::new("a", true),
+                Field::new("b", -1),
+                Field::new("c", u64::MAX),
+                Field::new("d", 0.5),
+                Field::new("e", f64::NAN),
+                Field::new("f", "x"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        assert_eq!(
+            r#"{"request_id":"b","user":1}"#,
+            object(&[
+                Field::new("request_id", "a"),
+                Field::new("user", 1),
+                Field::new("request_id", "b"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_to_json_without_fields() {
+        assert_eq!(
+            r#"{"time":"2025-06-07T08:09:10.000Z","level":"WARN","location":"db.rs:88","message":"slow query","fields":{}}"#,
+            to_json(&record(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_to_json_with_logger() {
+        let record = Record {
+            logger: String::from("db.pool"),
+            ..record(Vec::new())
+        };
+
+        assert!(to_json(&record).contains(r#""level":"WARN","logger":"db.pool","location""#));
+    }
+
+    #[test]
+    fn test_to_json_with_thread() {
+        let record = Record {
+            thread_name: Some(String::from("worker-1")),
+            thread_id: Some(3),
+            process_id: Some(4242),
+            ..record(Vec::new())
+        };
+
+        assert!(
+            to_json(&record).contains(
+                r#""level":"WARN","thread":"worker-1","thread_id":3,"pid":4242,"location""#
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_json_with_depth() {
+        let record = Record {
+            depth: 2,
+            ..record(Vec::new())
+        };
+
+        assert!(to_json(&record).contains(r#""level":"WARN","depth":2,"location""#));
+    }
+
+    #[test]
+    fn test_json_sink() {
+        let path = std::env::temp_dir().join(format!("apollo-json-{}.log", std::process::id()));
+        let sink = JsonSink::open(&path).unwrap();
+
+        sink.log(&record(vec![Field::new("ms", 1200)])).unwrap();
+        sink.log(&record(Vec::new())).unwrap();
+        drop(sink);
+
+        let output = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(2, lines.len());
+        assert!(lines[0].ends_with(r#""fields":{"ms":1200}}"#));
+    }
+}
@@ -1,3 +1,4 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Levels {
     DEBUG,
     INFO,
@@ -18,6 +19,18 @@ impl Levels {
             Self::NONE => u8::MAX,
         }
     }
+
+    /// Gets the name of the level in upper case, like `"WARN"`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DEBUG => "DEBUG",
+            Self::INFO => "INFO",
+            Self::WARN => "WARN",
+            Self::ERROR => "ERROR",
+            Self::CRITICAL => "CRITICAL",
+            Self::NONE => "NONE",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -33,4 +46,10 @@ mod tests {
         assert!(Levels::WARN.as_u8() > Levels::INFO.as_u8());
         assert!(Levels::INFO.as_u8() > Levels::DEBUG.as_u8());
     }
+
+    #[test]
+    fn test_name() {
+        assert_eq!("DEBUG", Levels::DEBUG.name());
+        assert_eq!("CRITICAL", Levels::CRITICAL.name());
+    }
 }
@@ -1,17 +1,90 @@
+pub mod ansi;
 pub mod background_colors;
+pub mod clock;
+pub mod color_choice;
+mod color_conversion;
+pub mod color_support;
+pub mod columns;
+pub mod context;
+pub mod field;
 pub mod font_mode;
 pub mod foreground_colors;
+pub mod group;
+pub mod html;
+pub mod json;
 pub mod levels;
+pub mod location;
+pub mod logfmt;
+pub mod logger;
+mod macros;
+pub mod markup;
+pub mod names;
+mod output;
+pub mod rate_limit;
+pub mod record;
+pub mod registry;
+pub mod repeats;
+pub mod sampling;
+pub mod sanitize;
+pub mod sink;
+pub mod span;
+pub mod style;
+pub mod styled;
+pub mod syslog;
+#[cfg(test)]
+mod test_utils;
+pub mod theme;
+pub mod time_mode;
+pub mod value;
 
-use crate::background_colors::BackgroundColors;
-use crate::font_mode::FontMode;
-use crate::foreground_colors::ForegroundColors;
+use crate::clock::{Clock, SystemClock};
+use crate::color_choice::{ColorChoice, Stream};
+use crate::color_support::ColorSupport;
+use crate::columns::Columns;
+use crate::field::Field;
+use crate::group::Group;
 use crate::levels::Levels;
-use chrono::Utc;
-use std::str::from_utf8;
+use crate::location::{Location, LocationFormat};
+use crate::logger::Logger;
+use crate::output::{Frame, Pending};
+use crate::rate_limit::{Callsites, RateLimit, Suppressed};
+use crate::registry::Registry;
+use crate::repeats::{Repeat, Repeats};
+use crate::sampling::Sampling;
+use crate::sanitize::Sanitize;
+use crate::sink::Sink;
+use crate::span::Span;
+use crate::theme::{LevelTheme, Theme};
+use crate::time_mode::{PreviousRecord, TimeMode, format_relative};
+use chrono::{DateTime, Utc};
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct Apollo {
     pub logging_level: Levels,
+    pub clock: Arc<dyn Clock>,
+    pub time_mode: TimeMode,
+    pub previous_record: PreviousRecord,
+    pub color_choice: ColorChoice,
+    pub color_support: ColorSupport,
+    pub theme: Theme,
+    /// Converts markup tags like `<b>` and `<red>` in messages to styles, off by default
+    /// so messages that contain such tags are printed as they are
+    pub markup: bool,
+    pub sinks: Vec<Arc<dyn Sink>>,
+    pub hyperlinks: bool,
+    pub sanitize: Sanitize,
+    pub registry: Registry,
+    pub columns: Columns,
+    pub location_format: LocationFormat,
+    pub rate_limit: Option<RateLimit>,
+    pub callsites: Callsites,
+    pub sampling: Sampling,
+    /// Holds back records that repeat the last record within this time,
+    /// they are summarized like `message repeated 5 times`
+    pub collapse_repeats: Option<Duration>,
+    pub repeats: Repeats,
 }
 
 impl Default for Apollo {
@@ -38,7 +111,7 @@ impl Apollo {
     /// use crate::apollo_logger::Apollo;
     /// use crate::apollo_logger::levels::Levels;
     ///
-    /// let l = Apollo { logging_level: Levels::INFO };
+    /// let l = Apollo { logging_level: Levels::INFO, ..Apollo::new() };
     ///
     /// l.debug("This message will NOT printed");
     /// l.warn("This message will be printed");
@@ -46,12 +119,62 @@ impl Apollo {
     pub fn new() -> Apollo {
         Apollo {
             logging_level: Levels::DEBUG,
+            clock: Arc::new(SystemClock::new()),
+            time_mode: TimeMode::WallClock,
+            previous_record: PreviousRecord::new(),
+            color_choice: ColorChoice::Auto,
+            color_support: ColorSupport::detect(),
+            theme: Theme::new(),
+            markup: false,
+            sinks: Vec::new(),
+            hyperlinks: true,
+            sanitize: Sanitize::none(),
+            registry: Registry::new(),
+            columns: Columns::none(),
+            location_format: LocationFormat::default(),
+            rate_limit: None,
+            callsites: Callsites::new(),
+            sampling: Sampling::all(),
+            collapse_repeats: None,
+            repeats: Repeats::new(),
         }
     }
 
-    /// Gets the current time in Day/Months/Year Hour:Minute:Second.Millisecond format
-    fn get_time_as_string(&self) -> String {
-        Utc::now().format("%D %H:%M:%S%.3f").to_string()
+    /// Gets the current time as configured by the time mode, this record becomes the previous record
+    ///
+    /// The wall-clock time is in [Month/Day/Year Hour:Minute:Second.Millisecond] format,
+    /// relative times are in [+Seconds.Milliseconds] format
+    fn get_time_as_string(&self, now: DateTime<Utc>) -> String {
+        let elapsed = self.clock.elapsed();
+        let previous = self.previous_record.replace(elapsed);
+
+        self.format_time(now, elapsed, previous)
+    }
+
+    /// Formats the time of a record like [`Apollo::get_time_as_string`], relative to the
+    /// elapsed time of the previous record
+    fn format_time(
+        &self,
+        now: DateTime<Utc>,
+        elapsed: Duration,
+        previous: Option<Duration>,
+    ) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        if self.time_mode.shows_wall_clock() {
+            parts.push(format!("[{}]", now.format("%D %H:%M:%S%.3f")));
+        }
+
+        if self.time_mode.shows_elapsed() {
+            parts.push(format!("[{}]", format_relative(elapsed)));
+        }
+
+        if self.time_mode.shows_since_previous() {
+            let since_previous = elapsed.saturating_sub(previous.unwrap_or_default());
+            parts.push(format!("[{}]", format_relative(since_previous)));
+        }
+
+        parts.join(" ")
     }
 
     /// Prints a message to the console with the DEBUG label_format
@@ -70,32 +193,7 @@ impl Apollo {
     /// l.warn("This is an debug message");
     /// ```
     pub fn debug(&self, s: &str) -> Option<String> {
-        // Check if the logging level is high enough
-        if self.logging_level.as_u8() > Levels::DEBUG.as_u8() {
-            return None;
-        }
-
-        // Get current time
-        let current_time: String = self.get_time_as_string();
-
-        // Get caller file and line number
-        let location = self
-            .get_caller_location()
-            .unwrap_or(String::from("Unknown:0"));
-
-        // Get colors to print
-        let date_format = ForegroundColors::bright_green();
-        let label_format = ForegroundColors::cyan();
-        let location_format = FontMode::italic();
-        let text_format = ForegroundColors::bright_cyan();
-
-        // Print to console
-        let message = format!(
-            "{date_format}[{current_time}]\x1B[0m {label_format}[ DEBUG ]\x1B[0m | {location_format}{location}\x1B[0m | {text_format}{s}\x1B[0m"
-        );
-        println!("{message}");
-
-        Some(message)
+        self.log(Levels::DEBUG, s)
     }
 
     /// Prints a message to the console with the INFO label_format
@@ -114,32 +212,7 @@ impl Apollo {
     /// l.info("This is an info message");
     /// ```
     pub fn info(&self, s: &str) -> Option<String> {
-        // Check if the logging level is high enough
-        if self.logging_level.as_u8() > Levels::INFO.as_u8() {
-            return None;
-        }
-
-        // Get current time is [day/month/year hour:minute:second] format
-        let current_time: String = self.get_time_as_string();
-
-        // Get caller file and line number
-        let location = self
-            .get_caller_location()
-            .unwrap_or(String::from("Unknown:0"));
-
-        // Get colors to print
-        let date_format = ForegroundColors::bright_green();
-        let label_format = ForegroundColors::blue();
-        let location_format = FontMode::italic();
-        let text_format = ForegroundColors::bright_white();
-
-        // Print to console
-        let message = format!(
-            "{date_format}[{current_time}]\x1B[0m {label_format}[ INFO  ]\x1B[0m | {location_format}{location}\x1B[0m | {text_format}{s}\x1B[0m"
-        );
-        println!("{message}");
-
-        Some(message)
+        self.log(Levels::INFO, s)
     }
 
     /// Prints a message to the console with the WARN label_format
@@ -158,32 +231,7 @@ impl Apollo {
     /// l.warn("This is an warning message");
     /// ```
     pub fn warn(&self, s: &str) -> Option<String> {
-        // Check if the logging level is high enough
-        if self.logging_level.as_u8() > Levels::WARN.as_u8() {
-            return None;
-        }
-
-        // Get current time is [day/month/year hour:minute:second] format
-        let current_time: String = self.get_time_as_string();
-
-        // Get caller file and line number
-        let location = self
-            .get_caller_location()
-            .unwrap_or(String::from("Unknown:0"));
-
-        // Get colors to print
-        let date_format = ForegroundColors::bright_green();
-        let label_format = ForegroundColors::yellow();
-        let location_format = FontMode::italic();
-        let text_format = ForegroundColors::yellow() + FontMode::bold();
-
-        // Print to console
-        let message: String = format!(
-            "{date_format}[{current_time}]\x1B[0m {label_format}[ WARN  ]\x1B[0m | {location_format}{location}\x1B[0m | {text_format}{s}\x1B[0m"
-        );
-        println!("{message}");
-
-        Some(message)
+        self.log(Levels::WARN, s)
     }
 
     /// Prints a message to the console with the ERROR label_format
@@ -202,32 +250,7 @@ impl Apollo {
     /// l.error("This is an error message");
     /// ```
     pub fn error(&self, s: &str) -> Option<String> {
-        // Check if the logging level is high enough
-        if self.logging_level.as_u8() > Levels::ERROR.as_u8() {
-            return None;
-        }
-
-        // Get current time is [day/month/year hour:minute:second] format
-        let current_time: String = self.get_time_as_string();
-
-        // Get caller file and line number
-        let location = self
-            .get_caller_location()
-            .unwrap_or(String::from("Unknown:0"));
-
-        // Get colors to print
-        let date_format = ForegroundColors::bright_green();
-        let label_format = ForegroundColors::red();
-        let location_format = FontMode::italic();
-        let text_format = ForegroundColors::red() + FontMode::bold();
-
-        // Print to console
-        let message: String = format!(
-            "{date_format}[{current_time}]\x1B[0m {label_format}[ ERROR ]\x1B[0m | {location_format}{location}\x1B[0m | {text_format}{s}\x1B[0m"
-        );
-        eprintln!("{message}");
-
-        Some(message)
+        self.log(Levels::ERROR, s)
     }
 
     /// Prints a message to the console with the CRITICAL label_format
@@ -246,40 +269,415 @@ impl Apollo {
     /// l.critical("This is an critical message");
     /// ```
     pub fn critical(&self, s: &str) -> Option<String> {
-        // Check if the logging level is high enough
-        if self.logging_level.as_u8() > Levels::CRITICAL.as_u8() {
-            return None;
+        self.log(Levels::CRITICAL, s)
+    }
+
+    /// Prints a message to the console with the label and colors of the given level
+    fn log(&self, level: Levels, s: &str) -> Option<String> {
+        self.log_with_fields(level, s, Vec::new())
+    }
+
+    /// Prints a message with key-value fields to the console, the fields are also passed to the sinks.
+    /// The `debug!`, `info!`, `warn!`, `error!` and `critical!` macros are shorthands for this
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::Apollo;
+    /// use crate::apollo_logger::field::Field;
+    /// use crate::apollo_logger::levels::Levels;
+    ///
+    /// let l = Apollo::new();
+    ///
+    /// l.log_with_fields(Levels::INFO, "login", vec![Field::new("user_id", 42), Field::new("ok", true)]);
+    /// ```
+    pub fn log_with_fields(&self, level: Levels, s: &str, fields: Vec<Field>) -> Option<String> {
+        self.write(level, "", s, fields)
+    }
+
+    /// Starts a span, which logs a start record at the INFO level now and an end record
+    /// with the elapsed time when the returned guard is dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::Apollo;
+    ///
+    /// let l = Apollo::new();
+    ///
+    /// {
+    ///     let _span = l.span("load_config"); // Prints `load_config started`
+    ///     l.info("Reading app.toml");
+    /// } // Prints `load_config finished in 1.234ms`
+    /// ```
+    pub fn span(&self, name: &str) -> Span<'_> {
+        self.span_with_fields(Levels::INFO, name, Vec::new())
+    }
+
+    /// Starts a span with the given level and fields, the end record also has an `elapsed_ms` field
+    pub fn span_with_fields(&self, level: Levels, name: &str, fields: Vec<Field>) -> Span<'_> {
+        Span::start(self, "", level, name, fields)
+    }
+
+    /// Logs a title at the INFO level and indents all following records of this thread
+    /// until the returned guard is dropped
+    ///
+    /// The console output shows the indentation with tree-drawing characters,
+    /// the records passed to the sinks have the number of groups as `depth`.
+    /// When the title is not logged, for example because of the logging level,
+    /// the following records are not indented
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::Apollo;
+    ///
+    /// let l = Apollo::new();
+    ///
+    /// {
+    ///     let _group = l.group("Migrating database");
+    ///     l.info("Creating tables"); // Printed as `├─ Creating tables`
+    ///     l.info("Adding indexes");
+    /// }
+    /// l.info("Done"); // No longer indented
+    /// ```
+    pub fn group(&self, title: &str) -> Group {
+        self.group_with_fields(Levels::INFO, title, Vec::new())
+    }
+
+    /// Starts a group with a title of the given level and fields
+    pub fn group_with_fields(&self, level: Levels, title: &str, fields: Vec<Field>) -> Group {
+        match self.write(level, "", title, fields) {
+            Some(_) => Group::enter(),
+            None => Group::skip(),
+        }
+    }
+
+    /// Creates a logger with a name that shares the level, sinks and settings of this logger
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::Apollo;
+    /// use std::sync::Arc;
+    ///
+    /// let l = Arc::new(Apollo::new());
+    /// let db = l.child("db").with_field("shard", 3);
+    ///
+    /// db.info("This message is logged by db with a shard field");
+    /// db.child("pool").warn("This message is logged by db.pool");
+    /// ```
+    pub fn child(self: &Arc<Self>, name: &str) -> Logger {
+        Logger::new(self.clone(), name)
+    }
+
+    /// Prints a message of a named logger, the name is left out for the root logger
+    pub(crate) fn write(
+        &self,
+        level: Levels,
+        logger: &str,
+        s: &str,
+        fields: Vec<Field>,
+    ) -> Option<String> {
+        // Print the summaries of repeats that are held back longer than the timeout and of
+        // intervals that ended, even if this record is filtered
+        let elapsed = self.clock.elapsed();
+        if let Some(timeout) = self.collapse_repeats
+            && let Some(repeat) = self.repeats.expire(elapsed, timeout)
+        {
+            self.emit_repeat(repeat);
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            for suppressed in self.callsites.expire(elapsed, rate_limit) {
+                self.emit_suppressed(suppressed);
+            }
         }
 
-        // Get current time is [day/month/year hour:minute:second] format
-        let current_time: String = self.get_time_as_string();
+        // Check if the logging level is high enough, named loggers can have their own level
+        let logging_level = self
+            .registry
+            .effective_level(logger)
+            .unwrap_or(self.logging_level);
+        if logging_level.as_u8() > level.as_u8() || !self.sampling.keep(level) {
+            return None;
+        }
 
         // Get caller file and line number
-        let location = self
-            .get_caller_location()
-            .unwrap_or(String::from("Unknown:0"));
+        let caller = self.get_caller_location();
 
-        // Get colors to print
-        let date_format = ForegroundColors::bright_green();
-        let label_format = ForegroundColors::bright_red();
-        let location_format = FontMode::italic();
-        let text_format = ForegroundColors::bright_white()
-            + BackgroundColors::bright_red()
-            + FontMode::bold()
-            + FontMode::underline();
-
-        // Print to console
-        let message: String = format!(
-            "{date_format}[{current_time}]\x1B[0m {label_format}[ CRIT  ]\x1B[0m | {location_format}{location}\x1B[0m | {text_format}{s}\x1B[0m"
+        // The fields of the context go before the fields of this record, the summaries of
+        // records that are held back keep the fields and depth of those records
+        let fields: Vec<Field> = context::fields().into_iter().chain(fields).collect();
+        let depth = group::depth();
+
+        // Count the record for its callsite, a new interval starts with the number of
+        // records that were suppressed in the previous one. Records without a location
+        // can not be told apart, so they are not limited
+        if let (Some(rate_limit), Some(callsite)) = (&self.rate_limit, &caller) {
+            let (allowed, suppressed) =
+                self.callsites
+                    .check(callsite, rate_limit, elapsed, level, || {
+                        let suppressed = Suppressed {
+                            count: 0,
+                            level,
+                            logger: logger.to_string(),
+                            fields: fields.clone(),
+                            depth,
+                            location: callsite.clone(),
+                            interval: rate_limit.interval,
+                        };
+                        let pending = self.pending(level, logger, Some(callsite.clone()), depth);
+
+                        (suppressed, pending)
+                    });
+
+            if let Some(suppressed) = suppressed {
+                self.emit_suppressed(suppressed);
+            }
+            if !allowed {
+                return None;
+            }
+        }
+
+        // Hold back repeats of the last record, the summary of the repeats is printed
+        // before the next different record. Records of different contexts are not repeats
+        if let Some(timeout) = self.collapse_repeats {
+            let record = Repeat {
+                level,
+                logger: logger.to_string(),
+                message: s.to_string(),
+                fields: fields.clone(),
+                location: caller.clone(),
+                depth,
+                count: 0,
+            };
+            let (repeated, previous) = self.repeats.check(record, elapsed, timeout);
+
+            if let Some(previous) = previous {
+                self.emit_repeat(previous);
+            }
+            if repeated {
+                // Prepare the summary once, in case it is never printed otherwise
+                self.repeats.keep_summary(|repeat| {
+                    self.pending(
+                        repeat.level,
+                        &repeat.logger,
+                        repeat.location.clone(),
+                        repeat.depth,
+                    )
+                });
+                return None;
+            }
+        }
+
+        self.emit(level, logger, s, fields, caller, depth)
+    }
+
+    /// Prints how many records of a callsite were suppressed, at their highest level
+    fn emit_suppressed(&self, suppressed: Suppressed) -> Option<String> {
+        let location = suppressed.location.format(&self.location_format);
+        let message = suppressed.summary(&location);
+        let mut fields = suppressed.fields;
+        fields.push(Field::new("suppressed", suppressed.count));
+
+        self.emit(
+            suppressed.level,
+            &suppressed.logger,
+            &message,
+            fields,
+            Some(suppressed.location),
+            suppressed.depth,
+        )
+    }
+
+    /// Prints how often a record was repeated, at its level and location
+    fn emit_repeat(&self, repeat: Repeat) -> Option<String> {
+        let message = repeat.summary();
+        let mut fields = repeat.fields;
+        fields.push(Field::new("repeated", repeat.count));
+
+        self.emit(
+            repeat.level,
+            &repeat.logger,
+            &message,
+            fields,
+            repeat.location,
+            repeat.depth,
+        )
+    }
+
+    /// Prints the summaries of records that are held back as repeats or suppressed by the
+    /// rate limit, then flushes the sinks
+    ///
+    /// Call this before the program exits, the error of the first sink that fails is returned.
+    /// Summaries that are not flushed are printed when the logger is dropped, with the time
+    /// at which they were first held back
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::Apollo;
+    /// use std::time::Duration;
+    ///
+    /// let l = Apollo { collapse_repeats: Some(Duration::from_secs(30)), ..Apollo::new() };
+    ///
+    /// for _ in 0..3 {
+    ///     l.warn("Retrying"); // Printed once
+    /// }
+    /// l.flush().unwrap(); // Prints `message repeated 2 times`
+    /// ```
+    pub fn flush(&self) -> io::Result<()> {
+        if let Some(repeat) = self.repeats.take() {
+            self.emit_repeat(repeat);
+        }
+        for suppressed in self.callsites.drain() {
+            self.emit_suppressed(suppressed);
+        }
+
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            if let Err(error) = sink.flush() {
+                result = result.and(Err(error));
+            }
+        }
+        result
+    }
+
+    /// Prints a record that passed all filters and passes it to the sinks
+    fn emit(
+        &self,
+        level: Levels,
+        logger: &str,
+        s: &str,
+        fields: Vec<Field>,
+        caller: Option<Location>,
+        depth: usize,
+    ) -> Option<String> {
+        let current_time = self.get_time_as_string(self.clock.now());
+        let output = self
+            .frame(level, logger, caller, depth, current_time)?
+            .finish(s, fields);
+        output.print(&self.sinks);
+
+        Some(output.line)
+    }
+
+    /// Prepares the frame of a summary that is printed when the logger is dropped,
+    /// without making it the previous record
+    fn pending(
+        &self,
+        level: Levels,
+        logger: &str,
+        caller: Option<Location>,
+        depth: usize,
+    ) -> Option<Pending> {
+        let current_time = self.format_time(
+            self.clock.now(),
+            self.clock.elapsed(),
+            self.previous_record.get(),
         );
-        eprintln!("{message}");
+        let frame = self.frame(level, logger, caller, depth, current_time)?;
+
+        Some(Pending::new(frame, &self.sinks))
+    }
+
+    /// Gets the parts of the line of a record that do not depend on its message
+    fn frame(
+        &self,
+        level: Levels,
+        logger: &str,
+        caller: Option<Location>,
+        depth: usize,
+        current_time: String,
+    ) -> Option<Frame> {
+        // Get the label and stream of the level
+        let (label, stream) = match level {
+            Levels::DEBUG => ("[ DEBUG ]", Stream::Stdout),
+            Levels::INFO => ("[ INFO  ]", Stream::Stdout),
+            Levels::WARN => ("[ WARN  ]", Stream::Stdout),
+            Levels::ERROR => ("[ ERROR ]", Stream::Stderr),
+            Levels::CRITICAL => ("[ CRIT  ]", Stream::Stderr),
+            Levels::NONE => return None,
+        };
+
+        // Get colors to print
+        let level_theme = self.theme.get(level)?;
+
+        let location = caller.as_ref().map_or(String::from("Unknown:0"), |caller| {
+            caller.format(&self.location_format)
+        });
+
+        // Get the thread and process columns, then the logger name, as far as they are enabled
+        let thread = std::thread::current();
+        let thread_name = thread.name().filter(|_| self.columns.thread_name);
+        let thread_id = self.columns.thread_id.then(columns::thread_id);
+        let process_id = self.columns.process_id.then(std::process::id);
+
+        let mut thread_columns: Vec<String> = Vec::new();
+        if self.columns.thread_name {
+            thread_columns.push(thread_name.unwrap_or("<unnamed>").to_string());
+        }
+        if let Some(thread_id) = thread_id {
+            thread_columns.push(format!("#{thread_id}"));
+        }
+        if let Some(process_id) = process_id {
+            thread_columns.push(format!("pid:{process_id}"));
+        }
+        let columns: Vec<String> = [thread_columns.join(" "), logger.to_string()]
+            .into_iter()
+            .filter(|column| !column.is_empty())
+            .collect();
 
-        Some(message)
+        // Convert the colors to ones the terminal supports, if colors are enabled for this stream
+        let support = self.color_support;
+        let console_theme = self.color_choice.use_colors(stream).then(|| LevelTheme {
+            date: level_theme.date.downsample(support),
+            label: level_theme.label.downsample(support),
+            location: level_theme.location.downsample(support),
+            message: level_theme.message.downsample(support),
+            fields: level_theme.fields.downsample(support),
+        });
+
+        // Make the location clickable, the link is dropped when the output is redirected
+        let linked_location = match &caller {
+            Some(caller) if self.use_hyperlinks(stream) => {
+                ansi::hyperlink(&caller.to_file_url(), &location)
+            }
+            _ => location.clone(),
+        };
+
+        Some(Frame {
+            level,
+            stream,
+            label,
+            time: self.clock.now(),
+            current_time,
+            logger: logger.to_string(),
+            columns,
+            location,
+            linked_location,
+            console_theme,
+            theme: level_theme.clone(),
+            color_support: support,
+            sanitize: self.sanitize,
+            markup: self.markup,
+            depth,
+            thread_name: thread_name.map(String::from),
+            thread_id,
+            process_id,
+            record: !self.sinks.is_empty(),
+        })
+    }
+
+    /// Checks if the location should be printed as a hyperlink to the given stream,
+    /// which requires colors and a terminal
+    fn use_hyperlinks(&self, stream: Stream) -> bool {
+        self.hyperlinks && self.color_choice.use_colors(stream) && stream.is_terminal()
     }
 
     /// Gets the filename and location of the parent function that called this function
-    fn get_caller_location(&self) -> Option<String> {
-        let mut caller_location: Option<String> = None;
+    fn get_caller_location(&self) -> Option<Location> {
+        let mut caller_location: Option<Location> = None;
 
         backtrace::trace(|frame| {
             backtrace::resolve_frame(frame, |symbol| {
@@ -305,16 +703,16 @@ impl Apollo {
                 }
 
                 // Filter out backtrace symbols
-                let symbol_str = from_utf8(symbol_name.unwrap().as_bytes()).unwrap();
-                if Self::filter_locations(symbol_str) {
+                let symbol_str = format!("{:#}", symbol_name.unwrap());
+                if Self::filter_locations(&symbol_str) {
                     return;
                 }
 
-                caller_location = Some(format!(
-                    "{}:{}",
-                    file_name.unwrap().file_name().unwrap().display(),
-                    line_number.unwrap()
-                ));
+                caller_location = Some(Location {
+                    column: symbol.colno(),
+                    function: Some(symbol_str),
+                    ..Location::new(file_name.unwrap(), line_number.unwrap())
+                });
             });
             caller_location.is_none()
         });
@@ -324,6 +722,8 @@ impl Apollo {
     // Check if location contains any of these blacklisted locations
     fn filter_locations(location: &str) -> bool {
         location.contains(".cargo\\registry")
+            || location.contains(".cargo/registry")
+            || location.contains("/rustc/")
             || location.contains("src\\libstd")
             || location.contains("src\\libcore")
             || location.contains("src\\backtrace")
@@ -336,6 +736,12 @@ impl Apollo {
             || location.contains("Apollo::warn")
             || location.contains("Apollo::info")
             || location.contains("Apollo::debug")
+            || location.contains("Apollo::log")
+            || location.contains("Apollo::write")
+            || location.contains("Apollo::span")
+            || location.contains("Apollo::group")
+            || location.contains("apollo_logger::logger::Logger::")
+            || location.contains("apollo_logger::span::")
     }
 }
 
@@ -343,6 +749,16 @@ impl Apollo {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::ansi::strip_ansi;
+    use crate::background_colors::BackgroundColors;
+    use crate::clock::ManualClock;
+    use crate::font_mode::FontMode;
+    use crate::foreground_colors::ForegroundColors;
+    use crate::location::PathMode;
+    use crate::style::Style;
+    use crate::test_utils::MemorySink;
+    use chrono::{Duration, TimeZone};
+    use std::time::Duration as StdDuration;
 
     /// Test if debug will log to console with default logger level
     #[test]
@@ -356,6 +772,7 @@ mod tests {
     fn test_debug_under_level() {
         let logger = Apollo {
             logging_level: Levels::INFO,
+            ..Apollo::new()
         };
         assert!(logger.debug("This is a test debug message").is_none());
     }
@@ -372,6 +789,7 @@ mod tests {
     fn test_info_under_level() {
         let logger = Apollo {
             logging_level: Levels::WARN,
+            ..Apollo::new()
         };
         assert!(logger.info("This is a test info message").is_none());
     }
@@ -388,6 +806,7 @@ mod tests {
     fn test_warn_under_level() {
         let logger = Apollo {
             logging_level: Levels::ERROR,
+            ..Apollo::new()
         };
         assert!(logger.warn("This is a test warning message").is_none());
     }
@@ -404,6 +823,7 @@ mod tests {
     fn test_error_under_level() {
         let logger = Apollo {
             logging_level: Levels::CRITICAL,
+            ..Apollo::new()
         };
         assert!(logger.error("This is a test error message").is_none());
     }
@@ -420,6 +840,7 @@ mod tests {
     fn test_critical_under_level() {
         let logger = Apollo {
             logging_level: Levels::NONE,
+            ..Apollo::new()
         };
         assert!(logger.critical("This is a test critical message").is_none());
     }
@@ -429,6 +850,7 @@ mod tests {
     fn test_logging_level_none() {
         let logger = Apollo {
             logging_level: Levels::NONE,
+            ..Apollo::new()
         };
         assert!(logger.debug("This is a test debug message").is_none());
         assert!(logger.info("This is a test info message").is_none());
@@ -443,4 +865,930 @@ mod tests {
 
         assert!(logger.debug("This is a test debug message").is_some());
     }
+
+    /// Test if the timestamp comes from the configured clock
+    #[test]
+    fn test_manual_clock_timestamp() {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 6, 29, 16, 49, 8).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            ..Apollo::new()
+        };
+
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("[06/29/25 16:49:08.000]"));
+
+        clock.advance(Duration::milliseconds(1500));
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("[06/29/25 16:49:09.500]"));
+    }
+
+    /// Test if the elapsed time since creation is printed instead of the wall-clock time
+    #[test]
+    fn test_time_mode_elapsed() {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 6, 29, 16, 49, 8).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            time_mode: TimeMode::Elapsed,
+            ..Apollo::new()
+        };
+
+        clock.advance(Duration::milliseconds(12345));
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("[+12.345s]"));
+        assert!(!message.contains("06/29/25"));
+
+        clock.advance(Duration::seconds(1));
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("[+13.345s]"));
+    }
+
+    /// Test if the time since the previous record is printed alongside the wall-clock time
+    #[test]
+    fn test_time_mode_wall_clock_and_since_previous() {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 6, 29, 16, 49, 8).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            time_mode: TimeMode::WallClockAndSincePrevious,
+            ..Apollo::new()
+        };
+
+        clock.advance(Duration::milliseconds(500));
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("[06/29/25 16:49:08.500] [+0.500s]"));
+
+        clock.advance(Duration::milliseconds(12));
+        let message = logger.warn("This is a test warning message").unwrap();
+        assert!(message.contains("[06/29/25 16:49:08.512] [+0.012s]"));
+
+        // Records under the logging level do not reset the previous record time
+        let logger = Apollo {
+            logging_level: Levels::WARN,
+            ..logger
+        };
+        clock.advance(Duration::milliseconds(100));
+        assert!(logger.info("This is a test info message").is_none());
+        clock.advance(Duration::milliseconds(100));
+        let message = logger.warn("This is a test warning message").unwrap();
+        assert!(message.contains("[+0.200s]"));
+    }
+
+    /// Test if no escape sequences are printed when colors are disabled
+    #[test]
+    fn test_color_choice_never() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        };
+
+        let message = logger.critical("This is a test critical message").unwrap();
+        assert!(!message.contains('\x1B'));
+        assert!(message.contains("[ CRIT  ] | "));
+        assert!(message.ends_with(" | This is a test critical message"));
+    }
+
+    /// Test if escape sequences are printed when colors are forced, even if the output is redirected
+    #[test]
+    fn test_color_choice_always() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            ..Apollo::new()
+        };
+
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("\x1B[34m[ INFO  ]\x1B[0m"));
+    }
+
+    /// Test if the configured theme is used for the log line
+    #[test]
+    fn test_custom_theme() {
+        let mut theme = Theme::monochrome();
+        theme.error.label = ForegroundColors::magenta() + FontMode::blinking();
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            theme,
+            ..Apollo::new()
+        };
+
+        let message = logger.error("This is a test error message").unwrap();
+        assert!(message.starts_with("\x1B[2m["));
+        assert!(message.contains("\x1B[35;5m[ ERROR ]\x1B[0m"));
+        assert!(message.contains("\x1B[1;4mThis is a test error message\x1B[0m"));
+    }
+
+    /// Test if styled values in a message do not reset the style of the rest of the message
+    #[test]
+    fn test_styled_value_in_message() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            ..Apollo::new()
+        };
+
+        let user_id = Style::from(FontMode::bold()).paint(42);
+        let message = logger.warn(&format!("User {user_id} failed")).unwrap();
+        assert!(message.ends_with("\x1B[33;1mUser \x1B[1m42\x1B[0m\x1B[33;1m failed\x1B[0m"));
+    }
+
+    /// Test if markup in a message is converted to escape sequences
+    #[test]
+    fn test_markup() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            markup: true,
+            ..Apollo::new()
+        };
+
+        let message = logger
+            .info("user <b>42</b> failed <red>auth</red>")
+            .unwrap();
+        assert!(message.ends_with(
+            "\x1B[97muser \x1B[1m42\x1B[0m\x1B[97m failed \x1B[31mauth\x1B[0m\x1B[97m\x1B[0m"
+        ));
+    }
+
+    /// Test if markup is removed when colors are disabled and kept when markup is disabled
+    #[test]
+    fn test_markup_without_colors() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            markup: true,
+            ..Apollo::new()
+        };
+        let message = logger
+            .info("user <b>42</b> failed <unknown>auth</unknown>")
+            .unwrap();
+        assert!(message.ends_with(" | user 42 failed <unknown>auth</unknown>"));
+
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        };
+        let message = logger.info("user <b>42</b> failed").unwrap();
+        assert!(message.ends_with(" | user <b>42</b> failed"));
+    }
+
+    /// Test if messages with tags reach the console and the sinks unchanged by default
+    #[test]
+    fn test_markup_is_opt_in() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        };
+
+        let message = logger.info("compare <b> and <s> tags").unwrap();
+        assert!(message.contains("compare <b> and <s> tags"));
+        assert!(!message.contains("\x1B[1m"));
+        assert_eq!(
+            "compare <b> and <s> tags",
+            sink.records.lock().unwrap()[0].message
+        );
+    }
+
+    /// Test if truecolors in the theme are converted to the colors the terminal supports
+    #[test]
+    fn test_color_support_downsampling() {
+        let mut theme = Theme::new();
+        theme.info.label = ForegroundColors::from_hex("#ff8800").unwrap().into();
+        theme.info.message = BackgroundColors::rgb(0, 0, 95) + FontMode::bold();
+
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            color_support: ColorSupport::Ansi256,
+            theme: theme.clone(),
+            ..Apollo::new()
+        };
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains("\x1B[38;5;208m[ INFO  ]"));
+        assert!(message.contains("\x1B[48;5;17;1mThis is a test info message"));
+
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            color_support: ColorSupport::None,
+            theme,
+            ..Apollo::new()
+        };
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains(" [ INFO  ] | "));
+        assert!(message.contains("\x1B[1mThis is a test info message"));
+    }
+
+    /// Test if records keep the full colors of the theme, even if the console has no colors
+    #[test]
+    fn test_sinks() {
+        let sink = Arc::new(MemorySink::default());
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            markup: true,
+            sinks: vec![sink.clone(), sink.clone()],
+            ..Apollo::new()
+        };
+
+        logger.warn("user <b>42</b>");
+        logger.debug("second");
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(4, records.len());
+        assert_eq!(Levels::WARN, records[0].level);
+        assert_eq!(clock.now(), records[0].time);
+        assert_eq!("user 42", records[0].message);
+        assert!(
+            records[0]
+                .line
+                .starts_with("\x1B[92m[01/02/24 03:04:05.000]\x1B[0m")
+        );
+        assert!(records[0].line.contains("\x1B[1m42\x1B[0m"));
+        assert_eq!(Levels::DEBUG, records[3].level);
+    }
+
+    /// Test if the caller of the log function is found and hyperlinks can be turned off
+    #[test]
+    fn test_caller_location() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            hyperlinks: false,
+            ..Apollo::new()
+        };
+
+        let message = logger.info("This is a test info message").unwrap();
+        assert!(message.contains(&format!("lib.rs:{}", line!() - 1)));
+        assert!(!message.contains("\x1B]8;;"));
+    }
+
+    /// Test if untrusted input can not forge log lines or change the terminal when sanitizing
+    #[test]
+    fn test_sanitize_forged_lines() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            markup: true,
+            sanitize: Sanitize::all(),
+            ..Apollo::new()
+        };
+
+        let message = logger
+            .info("user=bob\n[01/01/25 00:00:00.000] [ CRIT  ] | main.rs:1 | pwned\r\x1B[2K")
+            .unwrap();
+        assert_eq!(1, message.lines().count());
+        assert!(message.contains("user=bob\\n[01/01/25"));
+        assert!(message.contains("pwned\\r"));
+        assert!(!message.contains("\x1B[2K"));
+    }
+
+    /// Test if untrusted input can not inject styles through markup when sanitizing
+    #[test]
+    fn test_sanitize_disables_markup() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            markup: true,
+            sanitize: Sanitize::all(),
+            ..Apollo::new()
+        };
+
+        let message = logger.info("\x1B[31m<red><blink>pwned</blink>").unwrap();
+        assert!(message.contains("<red><blink>pwned</blink>"));
+        assert!(!message.contains("\x1B[31m"));
+        assert!(!message.contains("\x1B[5m"));
+
+        // Markup is only disabled when sanitizing markup
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            markup: true,
+            sanitize: Sanitize {
+                markup: false,
+                ..Sanitize::all()
+            },
+            ..Apollo::new()
+        };
+        let message = logger.info("<b>bold</b>").unwrap();
+        assert!(message.contains("\x1B[1mbold\x1B[0m"));
+    }
+
+    /// Test if fields are printed after the message and passed to the sinks
+    #[test]
+    fn test_fields() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Always,
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        };
+
+        let message = crate::info!(logger, "login", user_id = 42, ok = true, name = "a b").unwrap();
+        assert!(message.ends_with(" \x1B[2muser_id=42 ok=true name=\"a b\"\x1B[0m"));
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(
+            vec![
+                Field::new("user_id", 42),
+                Field::new("ok", true),
+                Field::new("name", "a b")
+            ],
+            records[0].fields
+        );
+        assert!(
+            records[0]
+                .line
+                .ends_with("\x1B[2muser_id=42 ok=true name=\"a b\"\x1B[0m")
+        );
+    }
+
+    /// Test if every level has a macro and fields are optional
+    #[test]
+    fn test_field_macros() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        };
+
+        assert!(
+            crate::debug!(logger, "debug")
+                .unwrap()
+                .ends_with(" | debug")
+        );
+        assert!(
+            crate::info!(logger, "info", a = 1,)
+                .unwrap()
+                .ends_with(" | info a=1")
+        );
+        assert!(
+            crate::warn!(logger, "warn", a = 'x')
+                .unwrap()
+                .ends_with(" | warn a=x")
+        );
+        assert!(
+            crate::error!(logger, "error", a = -1.5)
+                .unwrap()
+                .ends_with(" | error a=-1.5")
+        );
+        assert!(
+            crate::critical!(logger, "critical", a = String::from(""))
+                .unwrap()
+                .ends_with(" | critical a=\"\"")
+        );
+    }
+
+    /// Test if field values are sanitized like messages
+    #[test]
+    fn test_sanitize_fields() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sanitize: Sanitize::all(),
+            ..Apollo::new()
+        };
+
+        let message = crate::info!(logger, "login", user = "bob\n[ CRIT  ]\x1B[2J").unwrap();
+        assert_eq!(1, message.lines().count());
+        assert!(message.ends_with(" | login user=\"bob\\\\n[ CRIT  ]\""));
+    }
+
+    /// Test if fields of the context are added to every record in the scope
+    #[test]
+    fn test_context_fields() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        };
+
+        {
+            let _request = context::push("request_id", "7f3a");
+            let message = crate::info!(logger, "login", user_id = 42).unwrap();
+            assert!(message.ends_with(" | login request_id=7f3a user_id=42"));
+        }
+
+        let message = logger.info("done").unwrap();
+        assert!(message.ends_with(" | done"));
+    }
+
+    /// Test if named loggers use the level of their nearest configured ancestor
+    #[test]
+    fn test_registry_levels() {
+        let logger = Arc::new(Apollo {
+            logging_level: Levels::INFO,
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        });
+        logger.registry.set_level("app", Levels::DEBUG);
+        logger.registry.set_level("app.http", Levels::ERROR);
+
+        assert!(logger.child("app.http.client").warn("hidden").is_none());
+        assert!(logger.child("app.http.client").error("shown").is_some());
+        assert!(logger.child("app").child("db").debug("shown").is_some());
+        assert!(logger.child("other").debug("hidden").is_none());
+        assert!(logger.debug("hidden").is_none());
+
+        logger.registry.set_level("app.http", Levels::NONE);
+        assert!(logger.child("app.http").critical("hidden").is_none());
+    }
+
+    /// Test if the thread and process columns are printed and passed to the sinks
+    #[test]
+    fn test_columns() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Arc::new(Apollo {
+            color_choice: ColorChoice::Never,
+            columns: Columns::all(),
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        });
+
+        let worker = logger.clone();
+        let message = std::thread::Builder::new()
+            .name(String::from("worker-1"))
+            .spawn(move || worker.child("pool").info("started").unwrap())
+            .unwrap()
+            .join()
+            .unwrap();
+
+        let record = sink.records.lock().unwrap()[0].clone();
+        let thread_id = record.thread_id.unwrap();
+        assert_eq!(Some(String::from("worker-1")), record.thread_name);
+        assert_eq!(Some(std::process::id()), record.process_id);
+        assert!(message.contains(&format!(
+            " [ INFO  ] | worker-1 #{thread_id} pid:{} | pool | lib.rs:",
+            std::process::id()
+        )));
+
+        let unnamed = std::thread::spawn(move || logger.info("started").unwrap())
+            .join()
+            .unwrap();
+        assert!(unnamed.contains(" [ INFO  ] | <unnamed> #"));
+        assert_eq!(None, sink.records.lock().unwrap()[1].thread_name);
+    }
+
+    /// Test if the location can include the path, column and function of the caller
+    #[test]
+    fn test_location_format() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            location_format: LocationFormat {
+                path: PathMode::CrateRelative,
+                column: true,
+                function: true,
+            },
+            ..Apollo::new()
+        };
+
+        let line = line!() + 1;
+        let message = logger.info("located").unwrap();
+
+        // The column depends on the compiler, it points into the line of the call
+        assert!(message.contains(&format!(" | src/lib.rs:{line}:")));
+        assert!(message.ends_with(" in apollo_logger::tests::test_location_format | located"));
+    }
+
+    /// Test if the records of a span of a named logger point at the code that started and ended it
+    #[test]
+    fn test_span_records() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Arc::new(Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        });
+        let db = logger.child("db").with_field("shard", 3);
+
+        let start = line!() + 1;
+        let span = db.span_with_fields(Levels::WARN, "migrate", vec![Field::new("version", 7)]);
+        let end = line!() + 1;
+        drop(span);
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!("db", records[1].logger);
+        assert_eq!(Levels::WARN, records[1].level);
+        assert_eq!(format!("lib.rs:{start}"), records[0].location);
+        assert_eq!(format!("lib.rs:{end}"), records[1].location);
+        assert_eq!(
+            vec![Field::new("shard", 3), Field::new("version", 7)],
+            records[1].fields[..2]
+        );
+    }
+
+    /// Test if records in groups are indented with tree characters and have a depth
+    #[test]
+    fn test_group() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        };
+
+        let title_line = line!() + 1;
+        let group = logger.group("Migrating database");
+        let step = logger.info("Creating tables").unwrap();
+        let nested = {
+            let _span = logger.span("Adding indexes");
+            logger.debug("users").unwrap()
+        };
+        drop(group);
+        let after = logger.info("Done").unwrap();
+
+        assert!(step.ends_with(" | ├─ Creating tables"));
+        assert!(nested.ends_with(" | │  ├─ users"));
+        assert!(after.ends_with(" | Done"));
+
+        let records = sink.records.lock().unwrap();
+        let depths: Vec<usize> = records.iter().map(|record| record.depth).collect();
+        assert_eq!(vec![0, 1, 1, 2, 1, 0], depths);
+        assert_eq!(format!("lib.rs:{title_line}"), records[0].location);
+        assert_eq!("users", records[3].message);
+        assert!(strip_ansi(&records[3].line).ends_with(" | │  ├─ users"));
+    }
+
+    /// Test if a group whose title is filtered by the logging level does not indent its records
+    #[test]
+    fn test_filtered_group_is_not_entered() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            logging_level: Levels::INFO,
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            ..Apollo::new()
+        };
+
+        let group = logger.group_with_fields(
+            Levels::DEBUG,
+            "Migrating database",
+            vec![Field::new("tables", 3)],
+        );
+        let step = logger.info("Creating tables").unwrap();
+        drop(group);
+
+        assert!(step.ends_with(" | Creating tables"));
+        assert_eq!(0, group::depth());
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(1, records.len());
+        assert_eq!(0, records[0].depth);
+    }
+
+    /// Test if a callsite is limited per interval and reports how many records it suppressed
+    #[test]
+    fn test_rate_limit() {
+        let sink = Arc::new(MemorySink::default());
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            rate_limit: Some(RateLimit {
+                max: 2,
+                interval: StdDuration::from_secs(10),
+            }),
+            ..Apollo::new()
+        };
+        let log = |level| logger.log_with_fields(level, "connection refused", Vec::new());
+
+        let line = line!() - 2;
+        assert!(log(Levels::WARN).is_some());
+        assert!(log(Levels::WARN).is_some());
+        assert!(log(Levels::ERROR).is_none());
+        assert!(log(Levels::WARN).is_none());
+        assert!(logger.warn("other callsite").is_some());
+
+        clock.advance(Duration::seconds(10));
+        assert!(log(Levels::WARN).is_some());
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(5, records.len());
+        assert_eq!(Levels::ERROR, records[3].level);
+        assert_eq!(
+            format!("suppressed 2 messages from lib.rs:{line} in last 10s"),
+            records[3].message
+        );
+        assert_eq!(vec![Field::new("suppressed", 2u64)], records[3].fields);
+        assert_eq!("connection refused", records[4].message);
+    }
+
+    /// Test if the records suppressed in an interval are reported by the next record of any
+    /// callsite, with the context and depth of the suppressed records
+    #[test]
+    fn test_rate_limit_expires() {
+        let sink = Arc::new(MemorySink::default());
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            rate_limit: Some(RateLimit {
+                max: 1,
+                interval: StdDuration::from_secs(10),
+            }),
+            ..Apollo::new()
+        };
+        let log = || logger.warn("connection refused");
+
+        {
+            let _request = context::push("request_id", "7f3a");
+            let _group = Group::enter();
+            for _ in 0..3 {
+                log();
+            }
+        }
+        clock.advance(Duration::seconds(10));
+        assert!(logger.debug("other callsite").is_some());
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(3, records.len());
+        assert!(
+            records[1]
+                .message
+                .starts_with("suppressed 2 messages from lib.rs:")
+        );
+        assert_eq!(
+            vec![
+                Field::new("request_id", "7f3a"),
+                Field::new("suppressed", 2u64)
+            ],
+            records[1].fields
+        );
+        assert_eq!(1, records[1].depth);
+        assert_eq!("other callsite", records[2].message);
+        assert_eq!(0, records[2].depth);
+    }
+
+    /// Test if the records suppressed in the current interval are reported when the logger is dropped
+    #[test]
+    fn test_rate_limit_on_drop() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            rate_limit: Some(RateLimit {
+                max: 1,
+                interval: StdDuration::from_secs(10),
+            }),
+            ..Apollo::new()
+        };
+        let log = || logger.error("connection refused");
+
+        let line = line!() - 2;
+        for _ in 0..4 {
+            log();
+        }
+        drop(logger);
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(Levels::ERROR, records[1].level);
+        assert_eq!(
+            format!("suppressed 3 messages from lib.rs:{line} in last 10s"),
+            records[1].message
+        );
+        assert_eq!(vec![Field::new("suppressed", 3u64)], records[1].fields);
+    }
+
+    /// Test if flushing prints the records suppressed in the current interval
+    #[test]
+    fn test_flush_rate_limit() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            rate_limit: Some(RateLimit {
+                max: 1,
+                interval: StdDuration::from_secs(10),
+            }),
+            ..Apollo::new()
+        };
+        let log = || logger.warn("connection refused");
+
+        let line = line!() - 2;
+        for _ in 0..3 {
+            log();
+        }
+        logger.flush().unwrap();
+        logger.flush().unwrap();
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(
+            format!("suppressed 2 messages from lib.rs:{line} in last 10s"),
+            records[1].message
+        );
+    }
+
+    /// Test if sampled out records are dropped
+    #[test]
+    fn test_sampling() {
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sampling: Sampling {
+                debug: 0.0,
+                ..Sampling::all()
+            },
+            ..Apollo::new()
+        };
+
+        assert!(logger.debug("dropped").is_none());
+        assert!(logger.info("kept").is_some());
+    }
+
+    /// Test if repeated records are held back and summarized
+    #[test]
+    fn test_collapse_repeats() {
+        let sink = Arc::new(MemorySink::default());
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let logger = Apollo {
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+        let retry = || logger.warn("retrying");
+
+        let line = line!() - 2;
+        assert!(retry().is_some());
+        assert!(retry().is_none());
+        assert!(retry().is_none());
+        assert!(logger.error("gave up").is_some());
+
+        assert!(retry().is_some());
+        assert!(retry().is_none());
+        clock.advance(Duration::seconds(30));
+        assert!(retry().is_some());
+
+        assert!(retry().is_none());
+        logger.flush().unwrap();
+
+        let records = sink.records.lock().unwrap();
+        let messages: Vec<&str> = records
+            .iter()
+            .map(|record| record.message.as_str())
+            .collect();
+        assert_eq!(
+            vec![
+                "retrying",
+                "message repeated 2 times",
+                "gave up",
+                "retrying",
+                "message repeated 1 time",
+                "retrying",
+                "message repeated 1 time",
+            ],
+            messages
+        );
+        assert_eq!(Levels::WARN, records[1].level);
+        assert_eq!(format!("lib.rs:{line}"), records[1].location);
+        assert_eq!(vec![Field::new("repeated", 2u64)], records[1].fields);
+    }
+
+    /// Test if the same message in different contexts is not collapsed
+    #[test]
+    fn test_collapse_repeats_of_contexts() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+
+        for request_id in ["7f3a", "9c1e"] {
+            let _request = context::push("request_id", request_id);
+            assert!(logger.warn("retrying").is_some());
+        }
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(vec![Field::new("request_id", "9c1e")], records[1].fields);
+    }
+
+    /// Test if the summary has the context and depth of the repeats, not of the record that prints it
+    #[test]
+    fn test_collapse_repeats_summary_context() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+        let retry = || logger.warn("retrying");
+
+        {
+            let _request = context::push("request_id", "7f3a");
+            let _group = Group::enter();
+            retry();
+            retry();
+        }
+        let _request = context::push("request_id", "9c1e");
+        retry();
+
+        let records = sink.records.lock().unwrap();
+        let summary = &records[1];
+        assert_eq!("message repeated 1 time", summary.message);
+        assert_eq!(
+            vec![
+                Field::new("request_id", "7f3a"),
+                Field::new("repeated", 1u64)
+            ],
+            summary.fields
+        );
+        assert_eq!(1, summary.depth);
+        assert_eq!(vec![Field::new("request_id", "9c1e")], records[2].fields);
+        assert_eq!(0, records[2].depth);
+    }
+
+    /// Test if held back repeats do not change the time since the previous record
+    #[test]
+    fn test_collapse_repeats_since_previous() {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            clock: clock.clone(),
+            time_mode: TimeMode::SincePrevious,
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+        let retry = || logger.warn("retrying");
+
+        retry();
+        clock.advance(Duration::seconds(5));
+        retry();
+        clock.advance(Duration::seconds(5));
+        logger.error("gave up");
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!("message repeated 1 time", records[1].message);
+        assert!(strip_ansi(&records[1].line).starts_with("[+10.000s] "));
+        assert!(strip_ansi(&records[2].line).starts_with("[+0.000s] "));
+    }
+
+    /// Test if the summary is printed by the first record after the timeout, even if it is filtered
+    #[test]
+    fn test_collapse_repeats_timeout() {
+        let sink = Arc::new(MemorySink::default());
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let logger = Apollo {
+            logging_level: Levels::INFO,
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+
+        let retry = || logger.warn("retrying");
+
+        retry();
+        retry();
+        clock.advance(Duration::seconds(29));
+        assert!(logger.debug("filtered").is_none());
+        assert_eq!(1, sink.records.lock().unwrap().len());
+
+        clock.advance(Duration::seconds(1));
+        assert!(logger.debug("filtered").is_none());
+        assert_eq!(
+            "message repeated 1 time",
+            sink.records.lock().unwrap()[1].message
+        );
+    }
+
+    /// Test if the summary is printed when the logger is dropped without flushing
+    #[test]
+    fn test_collapse_repeats_on_drop() {
+        let sink = Arc::new(MemorySink::default());
+        let logger = Apollo {
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink.clone()],
+            collapse_repeats: Some(StdDuration::from_secs(30)),
+            ..Apollo::new()
+        };
+
+        for _ in 0..3 {
+            logger.warn("retrying");
+        }
+        drop(logger);
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!("message repeated 2 times", records[1].message);
+        assert_eq!(vec![Field::new("repeated", 2u64)], records[1].fields);
+    }
 }
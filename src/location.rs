@@ -0,0 +1,276 @@
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// How much of the path of a source file to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathMode {
+    /// Only the file name, e.g. `mod.rs`
+    #[default]
+    Basename,
+    /// Path within the crate, e.g. `src/db/mod.rs`
+    CrateRelative,
+    /// Path as the compiler recorded it, absolute for dependencies
+    Full,
+}
+
+/// Which details of the caller location to print
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::location::{LocationFormat, PathMode};
+///
+/// let l = Apollo {
+///     location_format: LocationFormat { path: PathMode::CrateRelative, column: true, function: true },
+///     ..Apollo::new()
+/// };
+///
+/// // Prints something like `[06/29/25 16:49:08.123] [ INFO  ] | src/main.rs:10:3 in my_app::main | Started`
+/// l.info("Started");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocationFormat {
+    pub path: PathMode,
+    /// Add the column after the line number
+    pub column: bool,
+    /// Add the demangled name of the calling function
+    pub function: bool,
+}
+
+/// Source location of the code that logged a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: Option<u32>,
+    /// Demangled function name without hash, like `my_app::db::connect`
+    pub function: Option<String>,
+}
+
+impl Location {
+    pub fn new(file: impl Into<PathBuf>, line: u32) -> Location {
+        Location {
+            file: file.into(),
+            line,
+            column: None,
+            function: None,
+        }
+    }
+
+    /// Formats the location with the given details, unknown details are left out
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::location::{Location, LocationFormat, PathMode};
+    ///
+    /// let location = Location {
+    ///     column: Some(9),
+    ///     function: Some(String::from("my_app::db::connect")),
+    ///     ..Location::new("src/db/mod.rs", 88)
+    /// };
+    /// let format = LocationFormat { path: PathMode::CrateRelative, column: true, function: true };
+    ///
+    /// assert_eq!("src/db/mod.rs:88:9 in my_app::db::connect", location.format(&format));
+    /// ```
+    pub fn format(&self, format: &LocationFormat) -> String {
+        let file = match format.path {
+            PathMode::Basename => self.basename(),
+            PathMode::CrateRelative => self.crate_relative_path().to_string_lossy().into_owned(),
+            PathMode::Full => self.file.to_string_lossy().into_owned(),
+        };
+        let mut result = format!("{file}:{}", self.line);
+
+        if let Some(column) = self.column.filter(|_| format.column) {
+            result.push_str(&format!(":{column}"));
+        }
+        if let Some(function) = self.function.as_ref().filter(|_| format.function) {
+            result.push_str(&format!(" in {function}"));
+        }
+        result
+    }
+
+    /// Gets the file name without its directories
+    fn basename(&self) -> String {
+        let file_name = self.file.file_name().unwrap_or(self.file.as_os_str());
+
+        file_name.to_string_lossy().into_owned()
+    }
+
+    /// Gets the path of the file within its crate
+    ///
+    /// The compiler records paths of the current crate relative to the workspace, they are kept.
+    /// Other paths are made relative to the working directory, or start at the directory
+    /// of the crate, which is the parent of the last `src` directory
+    fn crate_relative_path(&self) -> PathBuf {
+        if self.file.is_relative() {
+            return self.file.clone();
+        }
+
+        if let Some(relative) = std::env::current_dir().ok().and_then(|directory| {
+            self.file
+                .strip_prefix(directory)
+                .ok()
+                .map(Path::to_path_buf)
+        }) {
+            return relative;
+        }
+
+        let components: Vec<Component> = self.file.components().collect();
+        match components
+            .iter()
+            .rposition(|component| component.as_os_str() == "src")
+        {
+            Some(src) if src > 0 => components[src - 1..].iter().collect(),
+            _ => self.file.clone(),
+        }
+    }
+
+    /// Gets a `file://` URL to the absolute path of the file, with the line number as fragment
+    ///
+    /// Relative paths, as the compiler records them for the current crate,
+    /// are resolved against the working directory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::location::Location;
+    ///
+    /// let location = Location::new("/home/me/my app/main.rs", 42);
+    ///
+    /// assert_eq!("file:///home/me/my%20app/main.rs#42", location.to_file_url());
+    /// ```
+    pub fn to_file_url(&self) -> String {
+        let file = std::path::absolute(&self.file).unwrap_or_else(|_| self.file.clone());
+
+        format!("file://{}#{}", Self::encode_path(&file), self.line)
+    }
+
+    /// Percent-encodes a path for use in a URL, Windows paths get forward slashes
+    fn encode_path(path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let mut encoded = String::with_capacity(path.len() + 1);
+
+        // Windows paths like `C:/src/main.rs` need a slash before the drive letter
+        if !path.starts_with('/') {
+            encoded.push('/');
+        }
+
+        for byte in path.bytes() {
+            match byte {
+                b'A'..=b'Z'
+                | b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'-'
+                | b'.'
+                | b'_'
+                | b'~'
+                | b'/'
+                | b':' => encoded.push(byte as char),
+                _ => encoded.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        encoded
+    }
+}
+
+/// Prints the file name without its directories, followed by the line number
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.basename(), self.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("main.rs:42", Location::new("/src/main.rs", 42).to_string());
+        assert_eq!("main.rs:1", Location::new("main.rs", 1).to_string());
+    }
+
+    #[test]
+    fn test_format_paths() {
+        let location = Location::new(
+            "/home/me/.cargo/registry/src/index/db-1.0/src/pool/mod.rs",
+            5,
+        );
+        let format = |path| LocationFormat {
+            path,
+            ..LocationFormat::default()
+        };
+
+        assert_eq!("mod.rs:5", location.format(&format(PathMode::Basename)));
+        assert_eq!(
+            "db-1.0/src/pool/mod.rs:5",
+            location.format(&format(PathMode::CrateRelative))
+        );
+        assert_eq!(
+            "/home/me/.cargo/registry/src/index/db-1.0/src/pool/mod.rs:5",
+            location.format(&format(PathMode::Full))
+        );
+    }
+
+    #[test]
+    fn test_format_crate_relative() {
+        let directory = std::env::current_dir().unwrap();
+        let format = LocationFormat {
+            path: PathMode::CrateRelative,
+            ..LocationFormat::default()
+        };
+
+        assert_eq!(
+            "src/lib.rs:1",
+            Location::new("src/lib.rs", 1).format(&format)
+        );
+        assert_eq!(
+            "src/lib.rs:2",
+            Location::new(directory.join("src/lib.rs"), 2).format(&format)
+        );
+        assert_eq!("/main.rs:3", Location::new("/main.rs", 3).format(&format));
+    }
+
+    #[test]
+    fn test_format_unknown_details() {
+        let format = LocationFormat {
+            path: PathMode::Basename,
+            column: true,
+            function: true,
+        };
+
+        assert_eq!("lib.rs:1", Location::new("src/lib.rs", 1).format(&format));
+        assert_eq!(
+            "lib.rs:1",
+            Location::new("src/lib.rs", 1).format(&LocationFormat::default())
+        );
+    }
+
+    #[test]
+    fn test_file_url_absolute() {
+        assert_eq!(
+            "file:///src/%C3%A9t%C3%A9%20%231/lib.rs#7",
+            Location::new("/src/été #1/lib.rs", 7).to_file_url()
+        );
+    }
+
+    #[test]
+    fn test_file_url_relative() {
+        let directory = std::env::current_dir().unwrap();
+        let url = Location::new("src/lib.rs", 3).to_file_url();
+
+        assert!(url.starts_with("file:///"));
+        assert!(url.ends_with("/src/lib.rs#3"));
+        assert!(url.contains(&Location::encode_path(&directory)));
+    }
+
+    #[test]
+    fn test_encode_windows_path() {
+        assert_eq!(
+            "/C:/Users/me/main.rs",
+            Location::encode_path(Path::new("C:\\Users\\me\\main.rs"))
+        );
+    }
+}
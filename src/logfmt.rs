@@ -0,0 +1,174 @@
+use crate::field::{Field, quote};
+use crate::record::Record;
+use crate::sink::Sink;
+use chrono::SecondsFormat;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Converts a record to a logfmt line, the fields follow the message.
+/// The logger name is left out for the root logger, the thread and process only when they are known
+/// and the depth only inside groups
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::field::Field;
+/// use crate::apollo_logger::levels::Levels;
+/// use crate::apollo_logger::logfmt::to_logfmt;
+/// use crate::apollo_logger::record::Record;
+/// use chrono::{TimeZone, Utc};
+///
+/// let record = Record {
+///     location: String::from("main.rs:1"),
+///     fields: vec![Field::new("user_id", 42), Field::new("ok", true)],
+///     ..Record::new(Levels::INFO, Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(), "user logged in")
+/// };
+///
+/// assert_eq!(
+///     "time=2025-01-02T03:04:05.000Z level=info location=main.rs:1 message=\"user logged in\" user_id=42 ok=true",
+///     to_logfmt(&record)
+/// );
+/// ```
+pub fn to_logfmt(record: &Record) -> String {
+    let mut pairs = vec![
+        format!(
+            "time={}",
+            record.time.to_rfc3339_opts(SecondsFormat::Millis, true)
+        ),
+        format!("level={}", record.level.name().to_lowercase()),
+    ];
+    if !record.logger.is_empty() {
+        pairs.push(format!("logger={}", quote(&record.logger)));
+    }
+    if let Some(thread_name) = &record.thread_name {
+        pairs.push(format!("thread={}", quote(thread_name)));
+    }
+    if let Some(thread_id) = record.thread_id {
+        pairs.push(format!("thread_id={thread_id}"));
+    }
+    if let Some(process_id) = record.process_id {
+        pairs.push(format!("pid={process_id}"));
+    }
+    if record.depth > 0 {
+        pairs.push(format!("depth={}", record.depth));
+    }
+    pairs.push(format!("location={}", quote(&record.location)));
+    pairs.push(format!("message={}", quote(&record.message)));
+    pairs.extend(record.fields.iter().map(Field::to_string));
+
+    pairs.join(" ")
+}
+
+/// Sink that writes every record as a logfmt line
+pub struct LogfmtSink<W: Write + Send = File> {
+    writer: Mutex<W>,
+}
+
+impl LogfmtSink<File> {
+    /// Opens a file for appending, it is created if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<LogfmtSink<File>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(LogfmtSink::new(file))
+    }
+}
+
+impl<W: Write + Send> LogfmtSink<W> {
+    pub fn new(writer: W) -> LogfmtSink<W> {
+        LogfmtSink {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<W: Write + Send> Sink for LogfmtSink<W> {
+    fn log(&self, record: &Record) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+
+        writeln!(writer, "{}", to_logfmt(record))?;
+        writer.flush()
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::Levels;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+
+    fn record(message: &str, fields: Vec<Field>) -> Record {
+        let time = Utc.with_ymd_and_hms(2025, 6, 7, 8, 9, 10).unwrap();
+
+        Record {
+            location: String::from("db.rs:88"),
+            fields,
+            ..Record::new(Levels::ERROR, time, message)
+        }
+    }
+
+    #[test]
+    fn test_to_logfmt_quotes() {
+        assert_eq!(
+            "time=2025-06-07T08:09:10.000Z level=error location=db.rs:88 message=\"a\\nb\" query=\"select 1\"",
+            to_logfmt(&record("a\nb", vec![Field::new("query", "select 1")]))
+        );
+    }
+
+    #[test]
+    fn test_to_logfmt_with_logger() {
+        let record = Record {
+            logger: String::from("db"),
+            ..record("failed", Vec::new())
+        };
+
+        assert!(to_logfmt(&record).contains(" level=error logger=db location=db.rs:88 "));
+    }
+
+    #[test]
+    fn test_to_logfmt_with_thread() {
+        let record = Record {
+            thread_name: Some(String::from("worker 1")),
+            thread_id: Some(3),
+            process_id: Some(4242),
+            ..record("failed", Vec::new())
+        };
+
+        assert!(
+            to_logfmt(&record).contains(
+                " level=error thread=\"worker 1\" thread_id=3 pid=4242 location=db.rs:88 "
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_logfmt_with_depth() {
+        let record = Record {
+            depth: 1,
+            ..record("failed", Vec::new())
+        };
+
+        assert!(to_logfmt(&record).contains(" level=error depth=1 location=db.rs:88 "));
+    }
+
+    #[test]
+    fn test_logfmt_sink() {
+        let path = std::env::temp_dir().join(format!("apollo-logfmt-{}.log", std::process::id()));
+        let sink = LogfmtSink::open(&path).unwrap();
+
+        sink.log(&record("failed", vec![Field::new("retries", 3)]))
+            .unwrap();
+        drop(sink);
+
+        let output = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(output.ends_with(" message=failed retries=3\n"));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::Apollo;
+use crate::field::Field;
+use crate::group::Group;
+use crate::levels::Levels;
+use crate::span::Span;
+use crate::value::Value;
+use std::sync::Arc;
+
+/// Named logger that shares the level, sinks and settings of an `Apollo` instance
+/// and adds its own fields to every record, created with [`Apollo::child`]
+///
+/// Cloning a logger is cheap, the `Apollo` instance is shared
+#[derive(Clone)]
+pub struct Logger {
+    pub apollo: Arc<Apollo>,
+    /// Dotted name, like `db.pool`
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+impl Logger {
+    pub fn new(apollo: Arc<Apollo>, name: &str) -> Logger {
+        Logger {
+            apollo,
+            name: name.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Creates a logger with this name followed by a dot and the given name,
+    /// it starts with the fields of this logger
+    pub fn child(&self, name: &str) -> Logger {
+        Logger {
+            apollo: self.apollo.clone(),
+            name: format!("{}.{}", self.name, name),
+            fields: self.fields.clone(),
+        }
+    }
+
+    /// Adds a field to every record of this logger
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Logger {
+        self.fields.push(Field::new(key, value));
+        self
+    }
+
+    /// Adds fields to every record of this logger
+    pub fn with_fields(mut self, fields: Vec<Field>) -> Logger {
+        self.fields.extend(fields);
+        self
+    }
+
+    /// Prints a message with the DEBUG label
+    pub fn debug(&self, s: &str) -> Option<String> {
+        self.log_with_fields(Levels::DEBUG, s, Vec::new())
+    }
+
+    /// Prints a message with the INFO label
+    pub fn info(&self, s: &str) -> Option<String> {
+        self.log_with_fields(Levels::INFO, s, Vec::new())
+    }
+
+    /// Prints a message with the WARN label
+    pub fn warn(&self, s: &str) -> Option<String> {
+        self.log_with_fields(Levels::WARN, s, Vec::new())
+    }
+
+    /// Prints a message with the ERROR label
+    pub fn error(&self, s: &str) -> Option<String> {
+        self.log_with_fields(Levels::ERROR, s, Vec::new())
+    }
+
+    /// Prints a message with the CRITICAL label
+    pub fn critical(&self, s: &str) -> Option<String> {
+        self.log_with_fields(Levels::CRITICAL, s, Vec::new())
+    }
+
+    /// Logs a title at the INFO level and indents the following records, see [`Apollo::group`]
+    pub fn group(&self, title: &str) -> Group {
+        self.group_with_fields(Levels::INFO, title, Vec::new())
+    }
+
+    /// Starts a group of this logger with a title of the given level and fields,
+    /// after the fields of this logger
+    pub fn group_with_fields(&self, level: Levels, title: &str, fields: Vec<Field>) -> Group {
+        match self.log_with_fields(level, title, fields) {
+            Some(_) => Group::enter(),
+            None => Group::skip(),
+        }
+    }
+
+    /// Starts a span of this logger at the INFO level, see [`Apollo::span`]
+    pub fn span(&self, name: &str) -> Span<'_> {
+        self.span_with_fields(Levels::INFO, name, Vec::new())
+    }
+
+    /// Starts a span of this logger with the given level and fields, after the fields of this logger
+    pub fn span_with_fields(&self, level: Levels, name: &str, fields: Vec<Field>) -> Span<'_> {
+        let all = self.fields.iter().cloned().chain(fields).collect();
+
+        Span::start(&self.apollo, &self.name, level, name, all)
+    }
+
+    /// Prints a message with key-value fields, after the fields of this logger
+    pub fn log_with_fields(&self, level: Levels, s: &str, fields: Vec<Field>) -> Option<String> {
+        let all = self.fields.iter().cloned().chain(fields).collect();
+
+        self.apollo.write(level, &self.name, s, all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color_choice::ColorChoice;
+
+    fn apollo() -> Arc<Apollo> {
+        Arc::new(Apollo {
+            color_choice: ColorChoice::Never,
+            ..Apollo::new()
+        })
+    }
+
+    #[test]
+    fn test_child() {
+        let db = apollo().child("db").with_field("shard", 3);
+
+        let message = db.info("connected").unwrap();
+        assert!(message.contains(" [ INFO  ] | db | logger.rs:"));
+        assert!(message.ends_with(" | connected shard=3"));
+    }
+
+    #[test]
+    fn test_nested_child() {
+        let pool = apollo()
+            .child("db")
+            .with_field("shard", 3)
+            .child("pool")
+            .with_fields(vec![Field::new("size", 8)]);
+
+        assert_eq!("db.pool", pool.name);
+        let message = crate::warn!(pool, "exhausted", waiting = 2).unwrap();
+        assert!(message.contains(" | db.pool | "));
+        assert!(message.ends_with(" | exhausted shard=3 size=8 waiting=2"));
+    }
+
+    #[test]
+    fn test_child_shares_level() {
+        let apollo = Arc::new(Apollo {
+            logging_level: Levels::ERROR,
+            ..Apollo::new()
+        });
+        let db = apollo.child("db");
+
+        assert!(db.warn("not printed").is_none());
+        assert!(db.error("printed").is_some());
+    }
+}
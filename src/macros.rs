@@ -0,0 +1,112 @@
+/// Logs a message with key-value fields at the given level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::Apollo;
+/// use apollo_logger::levels::Levels;
+///
+/// let l = Apollo::new();
+///
+/// apollo_logger::log!(l, Levels::INFO, "login", user_id = 42, ok = true);
+/// ```
+#[macro_export]
+macro_rules! log {
+    ($logger:expr, $level:expr, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $logger.log_with_fields(
+            $level,
+            $message,
+            vec![$($crate::field::Field::new(stringify!($key), $value)),*],
+        )
+    };
+}
+
+/// Logs a message with key-value fields at the DEBUG level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::{Apollo, debug};
+///
+/// let l = Apollo::new();
+///
+/// debug!(l, "cache miss", key = "user:42");
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($logger:expr, $($arguments:tt)+) => {
+        $crate::log!($logger, $crate::levels::Levels::DEBUG, $($arguments)+)
+    };
+}
+
+/// Logs a message with key-value fields at the INFO level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::{Apollo, info};
+///
+/// let l = Apollo::new();
+///
+/// info!(l, "login", user_id = 42, ok = true);
+/// ```
+#[macro_export]
+macro_rules! info {
+    ($logger:expr, $($arguments:tt)+) => {
+        $crate::log!($logger, $crate::levels::Levels::INFO, $($arguments)+)
+    };
+}
+
+/// Logs a message with key-value fields at the WARN level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::{Apollo, warn};
+///
+/// let l = Apollo::new();
+///
+/// warn!(l, "slow query", ms = 1200);
+/// ```
+#[macro_export]
+macro_rules! warn {
+    ($logger:expr, $($arguments:tt)+) => {
+        $crate::log!($logger, $crate::levels::Levels::WARN, $($arguments)+)
+    };
+}
+
+/// Logs a message with key-value fields at the ERROR level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::{Apollo, error};
+///
+/// let l = Apollo::new();
+///
+/// error!(l, "request failed", status = 503, retry = true);
+/// ```
+#[macro_export]
+macro_rules! error {
+    ($logger:expr, $($arguments:tt)+) => {
+        $crate::log!($logger, $crate::levels::Levels::ERROR, $($arguments)+)
+    };
+}
+
+/// Logs a message with key-value fields at the CRITICAL level
+///
+/// # Examples
+///
+/// ```
+/// use apollo_logger::{Apollo, critical};
+///
+/// let l = Apollo::new();
+///
+/// critical!(l, "disk full", free_bytes = 0);
+/// ```
+#[macro_export]
+macro_rules! critical {
+    ($logger:expr, $($arguments:tt)+) => {
+        $crate::log!($logger, $crate::levels::Levels::CRITICAL, $($arguments)+)
+    };
+}
@@ -0,0 +1,242 @@
+use crate::background_colors::BackgroundColors;
+use crate::font_mode::FontMode;
+use crate::foreground_colors::ForegroundColors;
+use crate::style::Style;
+
+/// Piece of a message, either text or a known tag
+enum Token<'a> {
+    Text(&'a str),
+    Open(&'a str, Style),
+    Close(&'a str),
+}
+
+/// Gets the style of a markup tag, like `b`, `red` or `bg_blue`
+fn tag_style(name: &str) -> Option<Style> {
+    if let Some(color) = name.strip_prefix("bg_") {
+        let bg = match color {
+            "black" => BackgroundColors::black(),
+            "red" => BackgroundColors::red(),
+            "green" => BackgroundColors::green(),
+            "yellow" => BackgroundColors::yellow(),
+            "blue" => BackgroundColors::blue(),
+            "magenta" => BackgroundColors::magenta(),
+            "cyan" => BackgroundColors::cyan(),
+            "white" => BackgroundColors::white(),
+            "bright_black" => BackgroundColors::bright_black(),
+            "bright_red" => BackgroundColors::bright_red(),
+            "bright_green" => BackgroundColors::bright_green(),
+            "bright_yellow" => BackgroundColors::bright_yellow(),
+            "bright_blue" => BackgroundColors::bright_blue(),
+            "bright_magenta" => BackgroundColors::bright_magenta(),
+            "bright_cyan" => BackgroundColors::bright_cyan(),
+            "bright_white" => BackgroundColors::bright_white(),
+            _ => return None,
+        };
+        return Some(bg.into());
+    }
+
+    let style: Style = match name {
+        "b" | "bold" => FontMode::bold().into(),
+        "dim" => FontMode::dim().into(),
+        "i" | "italic" => FontMode::italic().into(),
+        "u" | "underline" => FontMode::underline().into(),
+        "blink" => FontMode::blinking().into(),
+        "reverse" => FontMode::reverse().into(),
+        "s" | "strike" => FontMode::strikethrough().into(),
+        "black" => ForegroundColors::black().into(),
+        "red" => ForegroundColors::red().into(),
+        "green" => ForegroundColors::green().into(),
+        "yellow" => ForegroundColors::yellow().into(),
+        "blue" => ForegroundColors::blue().into(),
+        "magenta" => ForegroundColors::magenta().into(),
+        "cyan" => ForegroundColors::cyan().into(),
+        "white" => ForegroundColors::white().into(),
+        "bright_black" => ForegroundColors::bright_black().into(),
+        "bright_red" => ForegroundColors::bright_red().into(),
+        "bright_green" => ForegroundColors::bright_green().into(),
+        "bright_yellow" => ForegroundColors::bright_yellow().into(),
+        "bright_blue" => ForegroundColors::bright_blue().into(),
+        "bright_magenta" => ForegroundColors::bright_magenta().into(),
+        "bright_cyan" => ForegroundColors::bright_cyan().into(),
+        "bright_white" => ForegroundColors::bright_white().into(),
+        _ => return None,
+    };
+    Some(style)
+}
+
+/// Splits a message into text and known tags, unknown tags are kept as text
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + length];
+
+        let token = match tag.strip_prefix('/') {
+            Some(name) if tag_style(name).is_some() => Some(Token::Close(name)),
+            Some(_) => None,
+            None => tag_style(tag).map(|style| Token::Open(tag, style)),
+        };
+
+        match token {
+            Some(token) => {
+                if start > 0 {
+                    tokens.push(Token::Text(&rest[..start]));
+                }
+                tokens.push(token);
+                rest = &rest[start + length + 1..];
+            }
+            None => {
+                // Keep the `<` of an unknown tag and look for the next tag after it
+                tokens.push(Token::Text(&rest[..start + 1]));
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// Converts markup tags in a message to escape sequences
+///
+/// Supported tags are `b`, `dim`, `i`, `u`, `blink`, `reverse`, `s`, the color names
+/// like `red` and `bright_blue` and background colors like `bg_red`.
+/// Unknown tags and closing tags without an opening tag are left as is
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::markup;
+///
+/// assert_eq!(
+///     "user \x1B[1m42\x1B[0m failed \x1B[31mauth\x1B[0m",
+///     markup::render("user <b>42</b> failed <red>auth</red>")
+/// );
+/// ```
+pub fn render(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut open: Vec<(&str, Style)> = Vec::new();
+
+    let combined = |open: &[(&str, Style)]| {
+        open.iter()
+            .fold(Style::new(), |style, (_, tag)| style + tag.clone())
+    };
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(text) => result.push_str(text),
+            Token::Open(name, style) => {
+                result.push_str(&style.to_string());
+                open.push((name, style));
+            }
+            Token::Close(name) => match open.iter().rposition(|(tag, _)| *tag == name) {
+                Some(index) => {
+                    // Reset everything and restore the tags that are still open
+                    open.remove(index);
+                    result.push_str("\x1B[0m");
+                    result.push_str(&combined(&open).to_string());
+                }
+                None => result.push_str(&format!("</{name}>")),
+            },
+        }
+    }
+
+    if !open.is_empty() {
+        result.push_str("\x1B[0m");
+    }
+    result
+}
+
+/// Removes markup tags from a message, for output that does not support colors
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::markup;
+///
+/// assert_eq!(
+///     "user 42 failed <auth>",
+///     markup::strip("user <b>42</b> failed <red><auth></red>")
+/// );
+/// ```
+pub fn strip(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut open: Vec<&str> = Vec::new();
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(text) => result.push_str(text),
+            Token::Open(name, _) => open.push(name),
+            Token::Close(name) => match open.iter().rposition(|tag| *tag == name) {
+                Some(index) => {
+                    open.remove(index);
+                }
+                None => result.push_str(&format!("</{name}>")),
+            },
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_font_modes() {
+        assert_eq!(
+            "\x1B[1mb\x1B[0m \x1B[3mi\x1B[0m \x1B[4mu\x1B[0m \x1B[9ms\x1B[0m",
+            render("<b>b</b> <i>i</i> <u>u</u> <s>s</s>")
+        );
+    }
+
+    #[test]
+    fn test_render_colors() {
+        assert_eq!(
+            "\x1B[96mcyan\x1B[0m on \x1B[44mblue\x1B[0m",
+            render("<bright_cyan>cyan</bright_cyan> on <bg_blue>blue</bg_blue>")
+        );
+    }
+
+    #[test]
+    fn test_render_nested() {
+        assert_eq!(
+            "\x1B[31mred \x1B[1mbold\x1B[0m\x1B[31m red\x1B[0m",
+            render("<red>red <b>bold</b> red</red>")
+        );
+    }
+
+    #[test]
+    fn test_render_unclosed() {
+        assert_eq!("\x1B[32mgreen\x1B[0m", render("<green>green"));
+    }
+
+    #[test]
+    fn test_render_unknown_tags() {
+        assert_eq!(
+            "<html> a < b </red> <unknown>x</unknown> <b",
+            render("<html> a < b </red> <unknown>x</unknown> <b")
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_tag_before_known_tag() {
+        assert_eq!("<\x1B[1mx\x1B[0m", render("<<b>x</b>"));
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(
+            "user 42 failed auth",
+            strip("user <b>42</b> failed <red>auth</red>")
+        );
+        assert_eq!("Vec<u8> <b", strip("Vec<u8> <b"));
+        assert_eq!("a < b </red>", strip("a < b </red>"));
+    }
+}
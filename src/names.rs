@@ -0,0 +1,143 @@
+use crate::color_conversion::parse_hex;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a name is not a known color, font mode or style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNameError {
+    name: String,
+}
+
+impl ParseNameError {
+    pub(crate) fn new(name: &str) -> ParseNameError {
+        ParseNameError {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown style name: {:?}", self.name)
+    }
+}
+
+impl Error for ParseNameError {}
+
+/// Names of the 16 basic colors, 0 to 7 are the normal and 8 to 15 the bright colors
+const COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+/// Color as it is written in a name, shared by the foreground and background colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorName {
+    Default,
+    Ansi16(u8),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl ColorName {
+    /// Parses a color name like `"bright_red"`, `"default"`, `"indexed(208)"` or `"#ff8800"`
+    pub(crate) fn parse(name: &str) -> Option<ColorName> {
+        let name = name.to_lowercase();
+
+        if name == "default" {
+            return Some(ColorName::Default);
+        }
+        if let Some(index) = COLOR_NAMES.iter().position(|color| *color == name) {
+            return Some(ColorName::Ansi16(index as u8));
+        }
+        if let Some(index) = name
+            .strip_prefix("indexed(")
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            return index.trim().parse().ok().map(ColorName::Indexed);
+        }
+        if name.starts_with('#') {
+            let (red, green, blue) = parse_hex(&name)?;
+            return Some(ColorName::Rgb(red, green, blue));
+        }
+        None
+    }
+}
+
+impl fmt::Display for ColorName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorName::Default => write!(f, "default"),
+            ColorName::Ansi16(index) => write!(f, "{}", COLOR_NAMES[*index as usize % 16]),
+            ColorName::Indexed(index) => write!(f, "indexed({index})"),
+            ColorName::Rgb(red, green, blue) => write!(f, "#{red:02x}{green:02x}{blue:02x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_name() {
+        assert_eq!(Some(ColorName::Default), ColorName::parse("default"));
+        assert_eq!(Some(ColorName::Ansi16(1)), ColorName::parse("red"));
+        assert_eq!(
+            Some(ColorName::Ansi16(15)),
+            ColorName::parse("Bright_White")
+        );
+        assert_eq!(
+            Some(ColorName::Indexed(208)),
+            ColorName::parse("indexed(208)")
+        );
+        assert_eq!(
+            Some(ColorName::Rgb(255, 136, 0)),
+            ColorName::parse("#ff8800")
+        );
+        assert_eq!(Some(ColorName::Rgb(255, 136, 0)), ColorName::parse("#F80"));
+    }
+
+    #[test]
+    fn test_parse_color_name_invalid() {
+        assert_eq!(None, ColorName::parse("orange"));
+        assert_eq!(None, ColorName::parse("indexed(256)"));
+        assert_eq!(None, ColorName::parse("#ff88"));
+        assert_eq!(None, ColorName::parse(""));
+    }
+
+    #[test]
+    fn test_color_name_round_trip() {
+        for name in [
+            "default",
+            "magenta",
+            "bright_cyan",
+            "indexed(17)",
+            "#010203",
+        ] {
+            assert_eq!(name, ColorName::parse(name).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_parse_name_error_display() {
+        assert_eq!(
+            "unknown style name: \"orange\"",
+            ParseNameError::new("orange").to_string()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use crate::ansi::strip_ansi;
+use crate::color_choice::Stream;
+use crate::color_support::{ColorSupport, downsample_sequence};
+use crate::field::Field;
+use crate::group;
+use crate::levels::Levels;
+use crate::markup;
+use crate::record::Record;
+use crate::sanitize::Sanitize;
+use crate::sink::Sink;
+use crate::theme::LevelTheme;
+use crate::value::Value;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Parts of a log line that do not depend on the message, like the time, label and location
+pub(crate) struct Frame {
+    pub(crate) level: Levels,
+    pub(crate) stream: Stream,
+    pub(crate) label: &'static str,
+    pub(crate) time: DateTime<Utc>,
+    pub(crate) current_time: String,
+    pub(crate) logger: String,
+    /// Columns in front of the location, like the thread and the logger name
+    pub(crate) columns: Vec<String>,
+    pub(crate) location: String,
+    /// Location for the console, which may be a hyperlink
+    pub(crate) linked_location: String,
+    /// Theme of the console downsampled to the color support, `None` if colors are disabled
+    pub(crate) console_theme: Option<LevelTheme>,
+    /// Theme of the line passed to the sinks
+    pub(crate) theme: LevelTheme,
+    pub(crate) color_support: ColorSupport,
+    pub(crate) sanitize: Sanitize,
+    /// Converts markup tags in the message to styles
+    pub(crate) markup: bool,
+    pub(crate) depth: usize,
+    pub(crate) thread_name: Option<String>,
+    pub(crate) thread_id: Option<u64>,
+    pub(crate) process_id: Option<u32>,
+    /// Builds the record for the sinks, which is left out if there are none
+    pub(crate) record: bool,
+}
+
+impl Frame {
+    /// Adds a message and fields to the frame, both are sanitized first
+    pub(crate) fn finish(&self, s: &str, fields: Vec<Field>) -> Output {
+        // Neutralize untrusted input before any escape sequences are added
+        let s = &self.sanitize.apply(s);
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .map(|field| self.sanitize_field(field))
+            .collect();
+        let fields_text = fields
+            .iter()
+            .map(Field::to_string)
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        // Sanitized messages may contain tags from untrusted input, which are printed as they are
+        let (rendered, stripped) = if self.markup && !self.sanitize.markup {
+            (markup::render(s), markup::strip(s))
+        } else {
+            (s.to_string(), s.to_string())
+        };
+
+        // Indent the message of the console and the line, the depth is passed to the sinks
+        let tree = group::tree(self.depth);
+        let columns: Vec<&str> = self.columns.iter().map(String::as_str).collect();
+
+        // Drop all escape sequences and markup if colors are disabled for this stream,
+        // otherwise convert the markup and colors to ones the terminal supports
+        let line = match &self.console_theme {
+            Some(console_theme) => paint_line(
+                console_theme,
+                &self.current_time,
+                self.label,
+                &columns,
+                &self.linked_location,
+                &format!(
+                    "{tree}{}",
+                    downsample_sequence(&rendered, self.color_support)
+                ),
+                &fields_text,
+            ),
+            None => paint_line(
+                &LevelTheme::plain(),
+                &self.current_time,
+                self.label,
+                &columns,
+                &self.location,
+                &format!("{tree}{stripped}"),
+                &fields_text,
+            ),
+        };
+
+        // Build the record for the sinks, which keep the full colors of the theme
+        let record = self.record.then(|| Record {
+            level: self.level,
+            time: self.time,
+            line: paint_line(
+                &self.theme,
+                &self.current_time,
+                self.label,
+                &columns,
+                &self.location,
+                &format!("{tree}{rendered}"),
+                &fields_text,
+            ),
+            logger: self.logger.clone(),
+            location: self.location.clone(),
+            message: strip_ansi(&stripped),
+            fields,
+            thread_name: self.thread_name.clone(),
+            thread_id: self.thread_id,
+            process_id: self.process_id,
+            depth: self.depth,
+        });
+
+        Output {
+            line,
+            stream: self.stream,
+            record,
+        }
+    }
+
+    /// Sanitizes the key and text value of a field like a message
+    fn sanitize_field(&self, field: Field) -> Field {
+        let value = match field.value {
+            Value::Str(value) => Value::Str(self.sanitize.apply(&value)),
+            value => value,
+        };
+
+        Field {
+            key: self.sanitize.apply(&field.key),
+            value,
+        }
+    }
+}
+
+/// Rendered record that is ready to be printed to the console and passed to the sinks
+pub(crate) struct Output {
+    pub(crate) line: String,
+    stream: Stream,
+    /// Record for the sinks, `None` if there were no sinks
+    record: Option<Record>,
+}
+
+impl Output {
+    /// Passes the record to the sinks and prints the line to its stream
+    pub(crate) fn print(&self, sinks: &[Arc<dyn Sink>]) {
+        if let Some(record) = &self.record {
+            for sink in sinks {
+                // A failing sink must not stop the message from reaching the console and other sinks
+                let _ = sink.log(record);
+            }
+        }
+
+        // Print to console
+        match self.stream {
+            Stream::Stdout => println!("{}", self.line),
+            Stream::Stderr => eprintln!("{}", self.line),
+        }
+    }
+}
+
+/// Frame of a summary that was not printed yet, with the sinks to print it to
+///
+/// Summaries that are still held back when the logger is dropped are printed with it,
+/// as the settings of the logger are gone by then
+pub(crate) struct Pending {
+    pub(crate) frame: Frame,
+    sinks: Vec<Arc<dyn Sink>>,
+}
+
+impl Pending {
+    pub(crate) fn new(frame: Frame, sinks: &[Arc<dyn Sink>]) -> Pending {
+        Pending {
+            frame,
+            sinks: sinks.to_vec(),
+        }
+    }
+
+    /// Prints the summary with its message and fields, then flushes the sinks
+    pub(crate) fn print(&self, s: &str, fields: Vec<Field>) {
+        self.frame.finish(s, fields).print(&self.sinks);
+
+        for sink in &self.sinks {
+            let _ = sink.flush();
+        }
+    }
+}
+
+/// Formats a log line with the styles of a level theme, the columns like
+/// the logger name go before the location and the fields are left out if there are none
+fn paint_line(
+    level_theme: &LevelTheme,
+    current_time: &str,
+    label: &str,
+    columns: &[&str],
+    location: &str,
+    message: &str,
+    fields: &str,
+) -> String {
+    let columns: String = columns
+        .iter()
+        .map(|column| format!("{} | ", level_theme.location.paint(column)))
+        .collect();
+    let line = format!(
+        "{} {} | {columns}{} | {}",
+        level_theme.date.paint(current_time),
+        level_theme.label.paint(label),
+        level_theme.location.paint(location),
+        level_theme.message.paint(message)
+    );
+
+    if fields.is_empty() {
+        return line;
+    }
+    format!("{line} {}", level_theme.fields.paint(fields))
+}
@@ -0,0 +1,362 @@
+use crate::field::Field;
+use crate::levels::Levels;
+use crate::location::Location;
+use crate::output::Pending;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Maximum number of records a single line of code can log per interval
+///
+/// When the next interval starts, a record tells how many records were suppressed,
+/// like `suppressed 312 messages from db.rs:88 in last 10s`. Like the summaries of repeats,
+/// it is printed by the next record after the interval, by [`crate::Apollo::flush`] or when
+/// the logger is dropped
+///
+/// Callsites are told apart by their file and line, so records whose location can not be
+/// resolved, for example in release builds without debug info, are never rate limited
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::rate_limit::RateLimit;
+/// use std::time::Duration;
+///
+/// let l = Apollo {
+///     rate_limit: Some(RateLimit { max: 5, interval: Duration::from_secs(10) }),
+///     ..Apollo::new()
+/// };
+///
+/// for _ in 0..100 {
+///     l.error("Connection refused"); // Printed 5 times
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max: u32,
+    pub interval: Duration,
+}
+
+/// Records that were suppressed at a callsite during an interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suppressed {
+    pub count: u64,
+    /// Highest level of the suppressed records
+    pub level: Levels,
+    /// Logger of the first suppressed record with the highest level
+    pub logger: String,
+    /// Fields of the context and the record of the first suppressed record with the highest level
+    pub fields: Vec<Field>,
+    /// Number of groups and spans the first suppressed record with the highest level was logged in
+    pub depth: usize,
+    /// Callsite of the suppressed records
+    pub location: Location,
+    pub interval: Duration,
+}
+
+impl Suppressed {
+    /// Gets the message that summarizes the suppressed records, with the formatted location
+    pub fn summary(&self, location: &str) -> String {
+        format!(
+            "suppressed {} messages from {location} in last {:?}",
+            self.count, self.interval
+        )
+    }
+}
+
+/// Interval of a callsite that is being counted, with the frame of the summary
+/// of its suppressed records
+struct Window {
+    start: Duration,
+    count: u32,
+    suppressed: Option<(Suppressed, Option<Pending>)>,
+}
+
+impl Window {
+    fn new(start: Duration) -> Window {
+        Window {
+            start,
+            count: 0,
+            suppressed: None,
+        }
+    }
+}
+
+/// Intervals of all callsites, with the end of the first interval that suppressed records
+#[derive(Default)]
+struct Windows {
+    windows: HashMap<String, Window>,
+    next_expiry: Option<Duration>,
+}
+
+/// Remembers how many records every callsite logged in its current interval
+///
+/// The records suppressed in an interval are reported by the next record of any callsite after
+/// the interval, by [`crate::Apollo::flush`], or when the callsites are dropped
+#[derive(Default)]
+pub struct Callsites {
+    windows: Mutex<Windows>,
+}
+
+impl fmt::Debug for Callsites {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let windows = self.windows.lock().unwrap();
+
+        f.debug_struct("Callsites")
+            .field("callsites", &windows.windows.len())
+            .field("next_expiry", &windows.next_expiry)
+            .finish()
+    }
+}
+
+impl Callsites {
+    pub fn new() -> Callsites {
+        Callsites::default()
+    }
+
+    /// Counts a record of a callsite at the given elapsed time and checks if it may be logged
+    ///
+    /// When a new interval starts, the records suppressed in the previous interval are returned.
+    /// The first suppressed record of an interval, and every one with a higher level than
+    /// the ones before, is described by `describe`
+    pub(crate) fn check(
+        &self,
+        callsite: &Location,
+        limit: &RateLimit,
+        now: Duration,
+        level: Levels,
+        describe: impl FnOnce() -> (Suppressed, Option<Pending>),
+    ) -> (bool, Option<Suppressed>) {
+        let key = format!("{}:{}", callsite.file.display(), callsite.line);
+        let mut windows = self.windows.lock().unwrap();
+        let Windows {
+            windows,
+            next_expiry,
+        } = &mut *windows;
+        let window = windows.entry(key).or_insert_with(|| Window::new(now));
+
+        let mut previous = None;
+        if now.saturating_sub(window.start) >= limit.interval {
+            previous = window.suppressed.take().map(|(suppressed, _)| suppressed);
+            *window = Window::new(now);
+        }
+
+        if window.count < limit.max {
+            window.count += 1;
+            return (true, previous);
+        }
+
+        match &mut window.suppressed {
+            Some((suppressed, _)) if level.as_u8() <= suppressed.level.as_u8() => {
+                suppressed.count += 1;
+            }
+            held => {
+                let count = held.as_ref().map_or(0, |(suppressed, _)| suppressed.count);
+                let (suppressed, pending) = describe();
+                *held = Some((
+                    Suppressed {
+                        count: count + 1,
+                        ..suppressed
+                    },
+                    pending,
+                ));
+
+                let end = window.start + limit.interval;
+                *next_expiry = Some(next_expiry.map_or(end, |next| next.min(end)));
+            }
+        }
+
+        (false, previous)
+    }
+
+    /// Takes the records suppressed in the intervals that ended, ordered by their location
+    pub fn expire(&self, now: Duration, limit: &RateLimit) -> Vec<Suppressed> {
+        let mut windows = self.windows.lock().unwrap();
+        if windows.next_expiry.is_none_or(|next| now < next) {
+            return Vec::new();
+        }
+
+        let mut expired = Vec::new();
+        windows.windows.retain(|_, window| {
+            if now.saturating_sub(window.start) < limit.interval {
+                return true;
+            }
+            expired.extend(window.suppressed.take().map(|(suppressed, _)| suppressed));
+            false
+        });
+        windows.next_expiry = windows
+            .windows
+            .values()
+            .filter(|window| window.suppressed.is_some())
+            .map(|window| window.start + limit.interval)
+            .min();
+
+        Self::sorted(expired)
+    }
+
+    /// Takes the records suppressed in the current intervals of all callsites,
+    /// ordered by their location
+    pub fn drain(&self) -> Vec<Suppressed> {
+        let mut windows = self.windows.lock().unwrap();
+        windows.next_expiry = None;
+
+        let suppressed = windows
+            .windows
+            .values_mut()
+            .filter_map(|window| window.suppressed.take())
+            .map(|(suppressed, _)| suppressed)
+            .collect();
+        Self::sorted(suppressed)
+    }
+
+    fn sorted(mut suppressed: Vec<Suppressed>) -> Vec<Suppressed> {
+        suppressed.sort_by(|a, b| {
+            (&a.location.file, a.location.line).cmp(&(&b.location.file, b.location.line))
+        });
+
+        suppressed
+    }
+}
+
+impl Drop for Callsites {
+    fn drop(&mut self) {
+        let Ok(windows) = self.windows.get_mut() else {
+            return;
+        };
+
+        for window in windows.windows.values_mut() {
+            if let Some((suppressed, Some(pending))) = window.suppressed.take() {
+                let mut fields = suppressed.fields.clone();
+                fields.push(Field::new("suppressed", suppressed.count));
+
+                pending.print(&suppressed.summary(&pending.frame.location), fields);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: RateLimit = RateLimit {
+        max: 2,
+        interval: Duration::from_secs(10),
+    };
+
+    fn check(
+        callsites: &Callsites,
+        location: &Location,
+        seconds: u64,
+        level: Levels,
+    ) -> (bool, Option<Suppressed>) {
+        callsites.check(
+            location,
+            &LIMIT,
+            Duration::from_secs(seconds),
+            level,
+            || {
+                let suppressed = Suppressed {
+                    count: 0,
+                    level,
+                    logger: format!("{level:?}"),
+                    fields: vec![Field::new("shard", 3)],
+                    depth: 1,
+                    location: location.clone(),
+                    interval: LIMIT.interval,
+                };
+
+                (suppressed, None)
+            },
+        )
+    }
+
+    #[test]
+    fn test_limit_per_interval() {
+        let callsites = Callsites::new();
+        let db = Location::new("db.rs", 88);
+
+        assert_eq!((true, None), check(&callsites, &db, 0, Levels::WARN));
+        assert_eq!((true, None), check(&callsites, &db, 1, Levels::WARN));
+        assert_eq!((false, None), check(&callsites, &db, 2, Levels::WARN));
+        assert_eq!((false, None), check(&callsites, &db, 3, Levels::ERROR));
+        assert_eq!((false, None), check(&callsites, &db, 9, Levels::WARN));
+
+        let suppressed = Suppressed {
+            count: 3,
+            level: Levels::ERROR,
+            logger: String::from("ERROR"),
+            fields: vec![Field::new("shard", 3)],
+            depth: 1,
+            location: db.clone(),
+            interval: LIMIT.interval,
+        };
+        assert_eq!(
+            (true, Some(suppressed)),
+            check(&callsites, &db, 10, Levels::WARN)
+        );
+        assert_eq!((true, None), check(&callsites, &db, 11, Levels::WARN));
+    }
+
+    #[test]
+    fn test_callsites_are_separate() {
+        let callsites = Callsites::new();
+        let (a1, a2) = (Location::new("a.rs", 1), Location::new("a.rs", 2));
+
+        for _ in 0..2 {
+            check(&callsites, &a1, 0, Levels::INFO);
+        }
+
+        assert!(!check(&callsites, &a1, 0, Levels::INFO).0);
+        assert!(check(&callsites, &a2, 0, Levels::INFO).0);
+    }
+
+    #[test]
+    fn test_expire() {
+        let callsites = Callsites::new();
+        let (a, b) = (Location::new("a.rs", 1), Location::new("b.rs", 1));
+        for _ in 0..3 {
+            check(&callsites, &a, 0, Levels::INFO);
+            check(&callsites, &b, 5, Levels::INFO);
+        }
+
+        assert!(callsites.expire(Duration::from_secs(9), &LIMIT).is_empty());
+
+        let expired = callsites.expire(Duration::from_secs(10), &LIMIT);
+        assert_eq!(
+            vec![a.clone()],
+            expired.into_iter().map(|s| s.location).collect::<Vec<_>>()
+        );
+        assert!(check(&callsites, &a, 10, Levels::INFO).0);
+
+        let expired = callsites.expire(Duration::from_secs(15), &LIMIT);
+        assert_eq!(
+            vec![b.clone()],
+            expired.into_iter().map(|s| s.location).collect::<Vec<_>>()
+        );
+        assert!(callsites.expire(Duration::from_secs(60), &LIMIT).is_empty());
+    }
+
+    #[test]
+    fn test_drain() {
+        let callsites = Callsites::new();
+        let (a, b) = (Location::new("a.rs", 1), Location::new("b.rs", 1));
+        for _ in 0..4 {
+            check(&callsites, &b, 0, Levels::INFO);
+            check(&callsites, &a, 0, Levels::INFO);
+        }
+
+        let drained = callsites.drain();
+        assert_eq!(
+            vec![(a.clone(), 2), (b.clone(), 2)],
+            drained
+                .into_iter()
+                .map(|suppressed| (suppressed.location, suppressed.count))
+                .collect::<Vec<_>>()
+        );
+        assert!(callsites.drain().is_empty());
+        assert!(!check(&callsites, &a, 0, Levels::INFO).0);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::field::Field;
+use crate::levels::Levels;
+use chrono::{DateTime, Utc};
+
+/// A single log message, as it is passed to the sinks of a logger
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub level: Levels,
+    pub time: DateTime<Utc>,
+    /// Dotted name of the logger, empty for the root logger
+    pub logger: String,
+    /// File name and line number of the code that logged the message
+    pub location: String,
+    /// Message after sanitizing, without markup and escape sequences
+    pub message: String,
+    /// Complete log line with the escape sequences of the theme, before color downsampling
+    pub line: String,
+    pub fields: Vec<Field>,
+    /// Name of the thread, if the thread has a name and the column is enabled
+    pub thread_name: Option<String>,
+    /// Numeric ID of the thread, if the column is enabled
+    pub thread_id: Option<u64>,
+    /// ID of the process, if the column is enabled
+    pub process_id: Option<u32>,
+    /// Number of groups and spans the record was logged in
+    pub depth: usize,
+}
+
+impl Record {
+    /// Creates a record of the root logger without location, fields or line,
+    /// the other fields can be set with struct update syntax
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::levels::Levels;
+    /// use crate::apollo_logger::record::Record;
+    /// use chrono::Utc;
+    ///
+    /// let record = Record {
+    ///     location: String::from("main.rs:1"),
+    ///     ..Record::new(Levels::INFO, Utc::now(), "started")
+    /// };
+    /// ```
+    pub fn new(level: Levels, time: DateTime<Utc>, message: impl Into<String>) -> Record {
+        Record {
+            level,
+            time,
+            logger: String::new(),
+            location: String::new(),
+            message: message.into(),
+            line: String::new(),
+            fields: Vec::new(),
+            thread_name: None,
+            thread_id: None,
+            process_id: None,
+            depth: 0,
+        }
+    }
+}
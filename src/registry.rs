@@ -0,0 +1,96 @@
+use crate::levels::Levels;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Logging levels of named loggers, a logger without a level uses the level of
+/// its nearest ancestor, so `app.http` also configures `app.http.client`
+///
+/// Loggers without a configured ancestor use the `logging_level` of `Apollo`. Only the levels
+/// are kept by name, the fields of a logger belong to the [`crate::logger::Logger`] value
+/// that [`crate::Apollo::child`] returns
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::levels::Levels;
+/// use std::sync::Arc;
+///
+/// let l = Arc::new(Apollo::new());
+/// l.registry.set_level("app.http", Levels::WARN);
+///
+/// let client = l.child("app.http.client");
+/// client.info("This message will NOT be printed");
+/// client.warn("This message will be printed");
+/// ```
+#[derive(Debug, Default)]
+pub struct Registry {
+    levels: RwLock<HashMap<String, Levels>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Sets the level of a logger and all its descendants without a level of their own
+    pub fn set_level(&self, name: &str, level: Levels) {
+        self.levels.write().unwrap().insert(name.to_string(), level);
+    }
+
+    /// Removes the level of a logger, it inherits the level of its ancestors again
+    pub fn clear_level(&self, name: &str) {
+        self.levels.write().unwrap().remove(name);
+    }
+
+    /// Gets the level that was set for exactly this logger
+    pub fn level(&self, name: &str) -> Option<Levels> {
+        self.levels.read().unwrap().get(name).copied()
+    }
+
+    /// Gets the level of a logger or of its nearest ancestor with a level
+    pub fn effective_level(&self, name: &str) -> Option<Levels> {
+        let levels = self.levels.read().unwrap();
+        let mut name = name;
+
+        loop {
+            if let Some(level) = levels.get(name) {
+                return Some(*level);
+            }
+            name = name.rsplit_once('.')?.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_level() {
+        let registry = Registry::new();
+        registry.set_level("app", Levels::INFO);
+        registry.set_level("app.http", Levels::ERROR);
+
+        assert_eq!(
+            Some(Levels::ERROR),
+            registry.effective_level("app.http.client")
+        );
+        assert_eq!(Some(Levels::ERROR), registry.effective_level("app.http"));
+        assert_eq!(Some(Levels::INFO), registry.effective_level("app.httpx"));
+        assert_eq!(Some(Levels::INFO), registry.effective_level("app.db"));
+        assert_eq!(None, registry.effective_level("other"));
+        assert_eq!(None, registry.effective_level(""));
+    }
+
+    #[test]
+    fn test_clear_level() {
+        let registry = Registry::new();
+        registry.set_level("app", Levels::INFO);
+        registry.set_level("app.http", Levels::ERROR);
+        registry.clear_level("app.http");
+
+        assert_eq!(None, registry.level("app.http"));
+        assert_eq!(Some(Levels::INFO), registry.effective_level("app.http"));
+    }
+}
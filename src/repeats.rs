@@ -0,0 +1,282 @@
+use crate::field::Field;
+use crate::levels::Levels;
+use crate::location::Location;
+use crate::output::Pending;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Record that was printed last, with the number of times it was repeated since
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeat {
+    pub level: Levels,
+    pub logger: String,
+    pub message: String,
+    /// Fields of the context followed by the fields of the record
+    pub fields: Vec<Field>,
+    pub location: Option<Location>,
+    /// Number of groups and spans the record was logged in
+    pub depth: usize,
+    pub count: u64,
+}
+
+impl Repeat {
+    /// Checks if another record has the same level, logger, message, fields, location and depth
+    fn is_repeated_by(&self, other: &Repeat) -> bool {
+        self.level == other.level
+            && self.logger == other.logger
+            && self.message == other.message
+            && self.fields == other.fields
+            && self.location == other.location
+            && self.depth == other.depth
+    }
+
+    /// Gets the message that summarizes the repeats, like `message repeated 5 times`
+    pub fn summary(&self) -> String {
+        match self.count {
+            1 => String::from("message repeated 1 time"),
+            count => format!("message repeated {count} times"),
+        }
+    }
+}
+
+/// Last printed record with the time it was printed and the frame of its summary
+struct Held {
+    repeat: Repeat,
+    since: Duration,
+    pending: Option<Pending>,
+}
+
+/// Remembers the last printed record, so records that repeat it can be held back
+/// and summarized like `message repeated 5 times`
+///
+/// The summary is printed by the next different record, by the next record after the
+/// timeout or by [`crate::Apollo::flush`]. If none of these happen, it is printed when
+/// the repeats are dropped, with the time of the first repeat
+#[derive(Default)]
+pub struct Repeats {
+    last: Mutex<Option<Held>>,
+}
+
+impl fmt::Debug for Repeats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.last.lock().unwrap();
+
+        f.debug_struct("Repeats")
+            .field(
+                "last",
+                &last.as_ref().map(|held| (&held.repeat, held.since)),
+            )
+            .finish()
+    }
+}
+
+impl Repeats {
+    pub fn new() -> Repeats {
+        Repeats::default()
+    }
+
+    /// Checks if a record repeats the last record within the timeout, repeats are counted
+    /// and should not be printed. Otherwise the record becomes the last record and the
+    /// previous one is returned if it was repeated
+    pub fn check(
+        &self,
+        record: Repeat,
+        now: Duration,
+        timeout: Duration,
+    ) -> (bool, Option<Repeat>) {
+        let mut last = self.last.lock().unwrap();
+
+        if let Some(held) = last.as_mut()
+            && held.repeat.is_repeated_by(&record)
+            && now.saturating_sub(held.since) < timeout
+        {
+            held.repeat.count += 1;
+            return (true, None);
+        }
+
+        let previous = last.replace(Held {
+            repeat: record,
+            since: now,
+            pending: None,
+        });
+        (
+            false,
+            previous
+                .map(|held| held.repeat)
+                .filter(|held| held.count > 0),
+        )
+    }
+
+    /// Takes the last record if it was repeated and printed longer than the timeout ago,
+    /// as no more repeats are counted for it
+    pub fn expire(&self, now: Duration, timeout: Duration) -> Option<Repeat> {
+        let mut last = self.last.lock().unwrap();
+
+        if last
+            .as_ref()
+            .is_some_and(|held| now.saturating_sub(held.since) >= timeout)
+        {
+            return last
+                .take()
+                .map(|held| held.repeat)
+                .filter(|held| held.count > 0);
+        }
+        None
+    }
+
+    /// Takes the last record if it was repeated, the next record is never a repeat
+    pub fn take(&self) -> Option<Repeat> {
+        self.last
+            .lock()
+            .unwrap()
+            .take()
+            .map(|held| held.repeat)
+            .filter(|held| held.count > 0)
+    }
+
+    /// Prepares the summary of the last record once it is repeated, to print it when the
+    /// repeats are dropped, later repeats only change the count
+    pub(crate) fn keep_summary(&self, prepare: impl FnOnce(&Repeat) -> Option<Pending>) {
+        if let Some(held) = self.last.lock().unwrap().as_mut()
+            && held.repeat.count > 0
+            && held.pending.is_none()
+        {
+            held.pending = prepare(&held.repeat);
+        }
+    }
+}
+
+impl Drop for Repeats {
+    fn drop(&mut self) {
+        let Ok(last) = self.last.get_mut() else {
+            return;
+        };
+
+        if let Some(Held {
+            repeat,
+            pending: Some(pending),
+            ..
+        }) = last.take()
+        {
+            let mut fields = repeat.fields.clone();
+            fields.push(Field::new("repeated", repeat.count));
+
+            pending.print(&repeat.summary(), fields);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn record(message: &str) -> Repeat {
+        Repeat {
+            level: Levels::WARN,
+            logger: String::new(),
+            message: String::from(message),
+            fields: Vec::new(),
+            location: Some(Location::new("db.rs", 88)),
+            depth: 0,
+            count: 0,
+        }
+    }
+
+    #[test]
+    fn test_repeats_until_other_message() {
+        let repeats = Repeats::new();
+        let now = Duration::ZERO;
+
+        assert_eq!((false, None), repeats.check(record("retry"), now, TIMEOUT));
+        assert_eq!((true, None), repeats.check(record("retry"), now, TIMEOUT));
+        assert_eq!((true, None), repeats.check(record("retry"), now, TIMEOUT));
+
+        let (repeated, previous) = repeats.check(record("gave up"), now, TIMEOUT);
+        assert!(!repeated);
+        assert_eq!(2, previous.unwrap().count);
+        assert_eq!(None, repeats.take());
+    }
+
+    #[test]
+    fn test_repeats_until_timeout() {
+        let repeats = Repeats::new();
+
+        repeats.check(record("retry"), Duration::ZERO, TIMEOUT);
+        repeats.check(record("retry"), Duration::from_secs(1), TIMEOUT);
+
+        let (repeated, previous) = repeats.check(record("retry"), TIMEOUT, TIMEOUT);
+        assert!(!repeated);
+        assert_eq!(1, previous.unwrap().count);
+    }
+
+    #[test]
+    fn test_differences_are_not_repeats() {
+        let repeats = Repeats::new();
+        let now = Duration::ZERO;
+        let other_location = Repeat {
+            location: Some(Location::new("db.rs", 89)),
+            ..record("retry")
+        };
+        let other_fields = Repeat {
+            fields: vec![Field::new("attempt", 2)],
+            ..record("retry")
+        };
+        let other_depth = Repeat {
+            depth: 1,
+            ..record("retry")
+        };
+
+        repeats.check(record("retry"), now, TIMEOUT);
+        assert!(!repeats.check(other_location, now, TIMEOUT).0);
+        assert!(!repeats.check(other_fields, now, TIMEOUT).0);
+
+        repeats.check(record("retry"), now, TIMEOUT);
+        assert!(!repeats.check(other_depth, now, TIMEOUT).0);
+    }
+
+    #[test]
+    fn test_expire() {
+        let repeats = Repeats::new();
+
+        repeats.check(record("retry"), Duration::ZERO, TIMEOUT);
+        assert_eq!(None, repeats.expire(TIMEOUT, TIMEOUT));
+
+        repeats.check(record("retry"), Duration::ZERO, TIMEOUT);
+        repeats.check(record("retry"), Duration::from_secs(1), TIMEOUT);
+        assert_eq!(None, repeats.expire(Duration::from_secs(4), TIMEOUT));
+        assert_eq!(1, repeats.expire(TIMEOUT, TIMEOUT).unwrap().count);
+        assert_eq!(None, repeats.take());
+    }
+
+    #[test]
+    fn test_summary() {
+        let once = Repeat {
+            count: 1,
+            ..record("retry")
+        };
+        let twice = Repeat {
+            count: 2,
+            ..record("retry")
+        };
+
+        assert_eq!("message repeated 1 time", once.summary());
+        assert_eq!("message repeated 2 times", twice.summary());
+    }
+
+    #[test]
+    fn test_take() {
+        let repeats = Repeats::new();
+        let now = Duration::ZERO;
+
+        repeats.check(record("retry"), now, TIMEOUT);
+        assert_eq!(None, repeats.take());
+
+        repeats.check(record("retry"), now, TIMEOUT);
+        repeats.check(record("retry"), now, TIMEOUT);
+        assert_eq!(1, repeats.take().unwrap().count);
+        assert!(!repeats.check(record("retry"), now, TIMEOUT).0);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::levels::Levels;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Fraction of the records of every level to keep, from `0.0` for none to `1.0` for all
+///
+/// Records that are not kept are dropped silently, before they reach the console or the sinks
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::sampling::Sampling;
+///
+/// // Keep one in a hundred DEBUG records and one in ten INFO records
+/// let l = Apollo { sampling: Sampling { debug: 0.01, info: 0.1, ..Sampling::all() }, ..Apollo::new() };
+///
+/// l.warn("This message will always be printed");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampling {
+    pub debug: f64,
+    pub info: f64,
+    pub warn: f64,
+    pub error: f64,
+    pub critical: f64,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Sampling {
+    /// Keeps all records
+    pub fn all() -> Sampling {
+        Sampling {
+            debug: 1.0,
+            info: 1.0,
+            warn: 1.0,
+            error: 1.0,
+            critical: 1.0,
+        }
+    }
+
+    /// Gets the fraction of records to keep for a level
+    pub fn rate(&self, level: Levels) -> f64 {
+        match level {
+            Levels::DEBUG => self.debug,
+            Levels::INFO => self.info,
+            Levels::WARN => self.warn,
+            Levels::ERROR => self.error,
+            Levels::CRITICAL => self.critical,
+            Levels::NONE => 0.0,
+        }
+    }
+
+    /// Decides at random if a record of a level is kept
+    pub fn keep(&self, level: Levels) -> bool {
+        let rate = self.rate(level);
+
+        rate >= 1.0 || (rate > 0.0 && random() < rate)
+    }
+}
+
+/// Gets a random number from `0.0` up to `1.0`
+///
+/// Every `RandomState` hashes with new keys, which is random enough for sampling
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_all_or_none() {
+        let sampling = Sampling {
+            debug: 0.0,
+            ..Sampling::all()
+        };
+
+        assert!((0..100).all(|_| sampling.keep(Levels::INFO)));
+        assert!((0..100).all(|_| !sampling.keep(Levels::DEBUG)));
+        assert!(!sampling.keep(Levels::NONE));
+    }
+
+    #[test]
+    fn test_keep_fraction() {
+        let sampling = Sampling {
+            error: 0.25,
+            ..Sampling::all()
+        };
+
+        let kept = (0..10_000).filter(|_| sampling.keep(Levels::ERROR)).count();
+        assert!((2_000..3_000).contains(&kept), "kept {kept} of 10000");
+    }
+
+    #[test]
+    fn test_random_range() {
+        assert!(
+            (0..1_000)
+                .map(|_| random())
+                .all(|n| (0.0..1.0).contains(&n))
+        );
+    }
+}
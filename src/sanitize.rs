@@ -0,0 +1,179 @@
+use crate::ansi::strip_ansi;
+
+/// What to neutralize in messages before they are printed, so untrusted input
+/// can not forge log lines or change the terminal
+///
+/// Escaped characters are printed as Rust escapes, like `\r` and `\u{1b}`. When anything is
+/// escaped, backslashes are escaped as well, so escapes can be told apart from the input
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::sanitize::Sanitize;
+///
+/// let l = Apollo { sanitize: Sanitize::all(), ..Apollo::new() };
+/// let user_name = "admin\n[01/01/25 00:00:00.000] [ INFO  ] | main.rs:1 | login ok";
+///
+/// let line = l.warn(&format!("Failed login for {user_name}")).unwrap();
+/// assert!(!line.contains('\n'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sanitize {
+    /// Remove escape sequences, like colors, cursor movement and hyperlinks
+    pub ansi: bool,
+    /// Escape control characters like `\r` and bidirectional text overrides, tabs are kept
+    pub control_characters: bool,
+    /// Escape newlines, carriage returns and the Unicode line and paragraph separators,
+    /// so every message is a single line
+    pub newlines: bool,
+    /// Print markup tags as they are, so untrusted input can not style the message
+    pub markup: bool,
+}
+
+impl Sanitize {
+    /// Leaves messages as they are
+    pub fn none() -> Sanitize {
+        Sanitize::default()
+    }
+
+    /// Neutralizes everything, for messages that contain untrusted input
+    pub fn all() -> Sanitize {
+        Sanitize {
+            ansi: true,
+            control_characters: true,
+            newlines: true,
+            markup: true,
+        }
+    }
+
+    /// Sanitizes a message or value, markup is disabled by the logger instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::sanitize::Sanitize;
+    ///
+    /// assert_eq!(
+    ///     "user \\r\\nroot",
+    ///     Sanitize::all().apply("user \x1B[2J\r\nroot")
+    /// );
+    /// ```
+    pub fn apply(&self, s: &str) -> String {
+        if *self == Sanitize::none() {
+            return s.to_string();
+        }
+
+        let s = if self.ansi {
+            strip_ansi(s)
+        } else {
+            s.to_string()
+        };
+        let escape = self.control_characters || self.newlines;
+        let mut result = String::with_capacity(s.len());
+
+        for c in s.chars() {
+            match c {
+                '\\' if escape => result.push_str("\\\\"),
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' if self.newlines => {
+                    result.extend(c.escape_default())
+                }
+                '\n' | '\t' => result.push(c),
+                c if self.control_characters && Self::is_control(c) => {
+                    result.extend(c.escape_default())
+                }
+                c => result.push(c),
+            }
+        }
+        result
+    }
+
+    /// Checks if a character is a control character or changes the direction of the text after it
+    fn is_control(c: char) -> bool {
+        c.is_control() || matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none() {
+        let s = "a\x1B[31mb\r\nc\u{202E}";
+
+        assert_eq!(s, Sanitize::none().apply(s));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let sanitize = Sanitize {
+            ansi: true,
+            ..Sanitize::none()
+        };
+
+        assert_eq!(
+            "red\nlink",
+            sanitize.apply("\x1B[31mred\x1B[0m\n\x1B]8;;evil\x1B\\link")
+        );
+    }
+
+    #[test]
+    fn test_control_characters() {
+        let sanitize = Sanitize {
+            control_characters: true,
+            ..Sanitize::none()
+        };
+
+        assert_eq!(
+            "\\u{1b}[2Ja\\rb\\u{7}\tc\nd\\u{9b}31m\\u{202e}",
+            sanitize.apply("\x1B[2Ja\rb\x07\tc\nd\u{9B}31m\u{202E}")
+        );
+    }
+
+    #[test]
+    fn test_newlines() {
+        let sanitize = Sanitize {
+            newlines: true,
+            ..Sanitize::none()
+        };
+
+        assert_eq!(
+            "a\\nb\\rc\\u{2028}d\\u{2029}e\u{7}",
+            sanitize.apply("a\nb\rc\u{2028}d\u{2029}e\u{7}")
+        );
+    }
+
+    #[test]
+    fn test_backslashes() {
+        let newlines = Sanitize {
+            newlines: true,
+            ..Sanitize::none()
+        };
+        let ansi = Sanitize {
+            ansi: true,
+            ..Sanitize::none()
+        };
+
+        assert_eq!("a\\\\nb\\nc", newlines.apply("a\\nb\nc"));
+        assert_eq!("a\\\\nb\\nc", Sanitize::all().apply("a\\nb\nc"));
+        assert_eq!("a\\nb", ansi.apply("a\\nb"));
+    }
+
+    #[test]
+    fn test_forged_line() {
+        let forged = "bob\n[01/01/25 00:00:00.000] [ CRIT  ] | main.rs:1 | disk full";
+
+        assert_eq!(
+            "bob\\n[01/01/25 00:00:00.000] [ CRIT  ] | main.rs:1 | disk full",
+            Sanitize::all().apply(forged)
+        );
+    }
+
+    #[test]
+    fn test_carriage_return_overwrite() {
+        let forged = "token=secret\r\x1B[2Kall good";
+
+        assert_eq!("token=secret\\rall good", Sanitize::all().apply(forged));
+    }
+}
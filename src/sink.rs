@@ -0,0 +1,13 @@
+use crate::record::Record;
+use std::io;
+
+/// Destination for log records besides the console, like a file
+pub trait Sink: Send + Sync {
+    /// Writes a record to the sink
+    fn log(&self, record: &Record) -> io::Result<()>;
+
+    /// Writes any buffered records
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
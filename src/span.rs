@@ -0,0 +1,182 @@
+use crate::Apollo;
+use crate::field::Field;
+use crate::group;
+use crate::levels::Levels;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Timed step of a program, logs a start record when it is created
+/// and an end record with the elapsed time when it is dropped
+///
+/// Like a [`group::Group`], the records inside a span are indented. Spans can not be sent to
+/// another thread, as the indentation belongs to the thread
+///
+/// When the start record is not logged, for example because of the logging level,
+/// the span does not indent the records inside it and does not log an end record
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct Span<'a> {
+    apollo: &'a Apollo,
+    logger: &'a str,
+    level: Levels,
+    name: String,
+    fields: Vec<Field>,
+    /// Depth to restore when the span ends, `None` if the start record was not logged
+    depth: Option<usize>,
+    start: Duration,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<'a> Span<'a> {
+    /// Starts a span of a named logger and logs its start record
+    pub(crate) fn start(
+        apollo: &'a Apollo,
+        logger: &'a str,
+        level: Levels,
+        name: &str,
+        fields: Vec<Field>,
+    ) -> Span<'a> {
+        let depth = group::depth();
+        let entered = apollo
+            .write(level, logger, &format!("{name} started"), fields.clone())
+            .is_some();
+        if entered {
+            group::set_depth(depth + 1);
+        }
+
+        Span {
+            apollo,
+            logger,
+            level,
+            name: name.to_string(),
+            fields,
+            depth: entered.then_some(depth),
+            start: apollo.clock.elapsed(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Gets the time since the span started
+    pub fn elapsed(&self) -> Duration {
+        self.apollo.clock.elapsed().saturating_sub(self.start)
+    }
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        let Some(depth) = self.depth else {
+            return;
+        };
+        group::set_depth(depth);
+
+        let elapsed = self.elapsed();
+        let message = format!("{} finished in {elapsed:.3?}", self.name);
+        let mut fields = std::mem::take(&mut self.fields);
+        fields.push(Field::new("elapsed_ms", elapsed.as_secs_f64() * 1000.0));
+
+        self.apollo.write(self.level, self.logger, &message, fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::color_choice::ColorChoice;
+    use crate::test_utils::MemorySink;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+
+    fn apollo(sink: Arc<MemorySink>) -> (Apollo, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ));
+        let apollo = Apollo {
+            clock: clock.clone(),
+            color_choice: ColorChoice::Never,
+            sinks: vec![sink],
+            ..Apollo::new()
+        };
+
+        (apollo, clock)
+    }
+
+    #[test]
+    fn test_span() {
+        let sink = Arc::new(MemorySink::default());
+        let (apollo, clock) = apollo(sink.clone());
+
+        let span = apollo.span_with_fields(
+            Levels::DEBUG,
+            "load_config",
+            vec![Field::new("path", "app.toml")],
+        );
+        clock.advance(chrono::Duration::milliseconds(1250));
+        assert_eq!(Duration::from_millis(1250), span.elapsed());
+        drop(span);
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(Levels::DEBUG, records[0].level);
+        assert_eq!("load_config started", records[0].message);
+        assert_eq!(vec![Field::new("path", "app.toml")], records[0].fields);
+        assert_eq!("load_config finished in 1.250s", records[1].message);
+        assert_eq!(
+            vec![
+                Field::new("path", "app.toml"),
+                Field::new("elapsed_ms", 1250.0)
+            ],
+            records[1].fields
+        );
+    }
+
+    #[test]
+    fn test_nested_spans() {
+        let sink = Arc::new(MemorySink::default());
+        let (apollo, _) = apollo(sink.clone());
+
+        {
+            let _outer = apollo.span("startup");
+            let _inner = apollo.span("load_config");
+        }
+        let _after = apollo.span("serve");
+
+        let records: Vec<(String, usize)> = sink
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|record| (record.message.clone(), record.depth))
+            .collect();
+        assert_eq!(
+            vec![
+                (String::from("startup started"), 0),
+                (String::from("load_config started"), 1),
+                (String::from("load_config finished in 0.000ns"), 1),
+                (String::from("startup finished in 0.000ns"), 0),
+                (String::from("serve started"), 0),
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn test_filtered_span_is_not_entered() {
+        let sink = Arc::new(MemorySink::default());
+        let (apollo, _) = apollo(sink.clone());
+        let apollo = Apollo {
+            logging_level: Levels::INFO,
+            ..apollo
+        };
+
+        {
+            let _span = apollo.span_with_fields(Levels::DEBUG, "load_config", Vec::new());
+            apollo.info("Reading app.toml");
+        }
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(1, records.len());
+        assert_eq!("Reading app.toml", records[0].message);
+        assert_eq!(0, records[0].depth);
+        assert_eq!(0, group::depth());
+    }
+}
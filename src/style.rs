@@ -0,0 +1,473 @@
+use crate::ansi::{ParseSgrError, sgr_parameters};
+use crate::background_colors::BackgroundColors;
+use crate::color_support::ColorSupport;
+use crate::font_mode::FontMode;
+use crate::foreground_colors::ForegroundColors;
+use crate::names::ParseNameError;
+use crate::styled::Styled;
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Combination of a foreground color, a background color and font modes
+///
+/// Styles are usually created by adding colors and font modes together,
+/// a later color replaces an earlier one and reset modes remove what they reset
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::font_mode::FontMode;
+/// use crate::apollo_logger::foreground_colors::ForegroundColors;
+///
+/// let style = ForegroundColors::cyan() + FontMode::bold() + ForegroundColors::magenta();
+///
+/// assert_eq!("\x1B[35;1m", style.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Style {
+    pub fg: Option<ForegroundColors>,
+    pub bg: Option<BackgroundColors>,
+    pub modes: Vec<FontMode>,
+}
+
+impl Style {
+    /// Creates an empty style, which prints nothing
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Checks if the style has no colors and no font modes
+    pub fn is_empty(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.modes.is_empty()
+    }
+
+    /// Creates a style from a space separated list of font modes and colors,
+    /// a color after `on` is the background color. `"none"` is the empty style
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::background_colors::BackgroundColors;
+    /// use crate::apollo_logger::font_mode::FontMode;
+    /// use crate::apollo_logger::foreground_colors::ForegroundColors;
+    /// use crate::apollo_logger::style::Style;
+    ///
+    /// let style = Style::from_name("bold underline yellow on blue").unwrap();
+    ///
+    /// assert_eq!(
+    ///     ForegroundColors::yellow() + BackgroundColors::blue() + FontMode::bold() + FontMode::underline(),
+    ///     style
+    /// );
+    /// assert_eq!("bold underline yellow on blue", style.name());
+    /// ```
+    pub fn from_name(name: &str) -> Result<Style, ParseNameError> {
+        let mut style = Style::new();
+        let mut words = name.split_whitespace();
+
+        if name.trim().eq_ignore_ascii_case("none") {
+            return Ok(style);
+        }
+
+        while let Some(word) = words.next() {
+            style = if word.eq_ignore_ascii_case("on") {
+                let bg = words.next().ok_or_else(|| ParseNameError::new(name))?;
+                style + BackgroundColors::from_name(bg).map_err(|_| ParseNameError::new(name))?
+            } else if let Ok(mode) = FontMode::from_name(word) {
+                style + mode
+            } else {
+                style + ForegroundColors::from_name(word).map_err(|_| ParseNameError::new(name))?
+            };
+        }
+
+        Ok(style)
+    }
+
+    /// Gets the name of the style, which `from_name` parses back to the same style
+    pub fn name(&self) -> String {
+        let mut words: Vec<String> = self
+            .modes
+            .iter()
+            .map(|mode| mode.name().to_string())
+            .collect();
+
+        if let Some(fg) = &self.fg {
+            words.push(fg.name());
+        }
+        if let Some(bg) = &self.bg {
+            words.push(format!("on {}", bg.name()));
+        }
+
+        if words.is_empty() {
+            return String::from("none");
+        }
+        words.join(" ")
+    }
+
+    /// Converts the colors of the style to the nearest colors the terminal supports
+    pub fn downsample(&self, support: ColorSupport) -> Style {
+        Style {
+            fg: self.fg.clone().and_then(|fg| fg.downsample(support)),
+            bg: self.bg.clone().and_then(|bg| bg.downsample(support)),
+            modes: self.modes.clone(),
+        }
+    }
+
+    /// Wraps a value so it is printed with this style, followed by a reset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::apollo_logger::font_mode::FontMode;
+    /// use crate::apollo_logger::style::Style;
+    ///
+    /// let user_id = Style::from(FontMode::bold()).paint(42);
+    ///
+    /// assert_eq!("\x1B[1m42\x1B[0m", user_id.to_string());
+    /// ```
+    pub fn paint<T: fmt::Display>(&self, value: T) -> Styled<T> {
+        Styled::new(self.clone(), value)
+    }
+
+    /// Gets the SGR parameters of the style, without the escape sequence around them
+    pub fn parameters(&self) -> String {
+        let mut parameters: Vec<String> = Vec::new();
+
+        if let Some(fg) = &self.fg {
+            parameters.push(fg.parameters());
+        }
+        if let Some(bg) = &self.bg {
+            parameters.push(bg.parameters());
+        }
+        parameters.extend(self.modes.iter().map(FontMode::parameters));
+
+        parameters.join(";")
+    }
+
+    /// Applies SGR parameters on top of this style like a terminal would,
+    /// parameters that are not supported are skipped
+    pub(crate) fn apply_parameters(self, parameters: &[u8]) -> Style {
+        let mut style = self;
+        let mut rest = parameters;
+
+        while !rest.is_empty() {
+            match style.apply_first_parameter(rest) {
+                Some((applied, length)) => {
+                    style = applied;
+                    rest = &rest[length..];
+                }
+                None => rest = &rest[1..],
+            }
+        }
+
+        style
+    }
+
+    /// Applies the color or font mode at the start of a list of SGR parameters,
+    /// returns the new style and the number of parameters it used
+    fn apply_first_parameter(&self, parameters: &[u8]) -> Option<(Style, usize)> {
+        if let Some((fg, length)) = ForegroundColors::from_parameters(parameters) {
+            Some((self.clone() + fg, length))
+        } else if let Some((bg, length)) = BackgroundColors::from_parameters(parameters) {
+            Some((self.clone() + bg, length))
+        } else {
+            FontMode::from_parameters(parameters)
+                .map(|(mode, length)| (self.clone() + mode, length))
+        }
+    }
+
+    fn add_mode(mut self, mode: FontMode) -> Self {
+        match mode {
+            FontMode::ResetAll(_) => return Style::new(),
+            // Bold and dim share their reset code
+            FontMode::ResetBold(_) | FontMode::ResetDim(_) => self
+                .modes
+                .retain(|m| !matches!(m, FontMode::Bold(_) | FontMode::Dim(_))),
+            FontMode::ResetItalic(_) => self.modes.retain(|m| !matches!(m, FontMode::Italic(_))),
+            FontMode::ResetUnderline(_) => {
+                self.modes.retain(|m| !matches!(m, FontMode::Underline(_)))
+            }
+            FontMode::ResetBlinking(_) => {
+                self.modes.retain(|m| !matches!(m, FontMode::Blinking(_)))
+            }
+            FontMode::ResetReverse(_) => self.modes.retain(|m| !matches!(m, FontMode::Reverse(_))),
+            FontMode::ResetInvisible(_) => {
+                self.modes.retain(|m| !matches!(m, FontMode::Invisible(_)))
+            }
+            FontMode::ResetStrikethrough(_) => self
+                .modes
+                .retain(|m| !matches!(m, FontMode::Strikethrough(_))),
+            mode => {
+                if !self.modes.contains(&mode) {
+                    self.modes.push(mode);
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Prints the style as a single SGR sequence, or nothing if the style is empty
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\x1B[{}m", self.parameters())
+    }
+}
+
+impl FromStr for Style {
+    type Err = ParseSgrError;
+
+    /// Parses a single SGR sequence like `"\x1B[38;5;208;44;1m"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parameters = sgr_parameters(s)?;
+        let mut style = Style::new();
+        let mut rest = parameters.as_slice();
+
+        while !rest.is_empty() {
+            let (applied, length) = style
+                .apply_first_parameter(rest)
+                .ok_or_else(|| ParseSgrError::new(s))?;
+            style = applied;
+            rest = &rest[length..];
+        }
+
+        Ok(style)
+    }
+}
+
+/// Serializes the style as its name, like `"bold red on black"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Style {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Style {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Style::from_name(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<ForegroundColors> for Style {
+    fn from(fg: ForegroundColors) -> Self {
+        Style {
+            fg: Some(fg),
+            ..Style::new()
+        }
+    }
+}
+
+impl From<BackgroundColors> for Style {
+    fn from(bg: BackgroundColors) -> Self {
+        Style {
+            bg: Some(bg),
+            ..Style::new()
+        }
+    }
+}
+
+impl From<FontMode> for Style {
+    fn from(mode: FontMode) -> Self {
+        Style::new().add_mode(mode)
+    }
+}
+
+impl Add<ForegroundColors> for Style {
+    type Output = Style;
+
+    fn add(mut self, rhs: ForegroundColors) -> Self::Output {
+        self.fg = Some(rhs);
+        self
+    }
+}
+
+impl Add<BackgroundColors> for Style {
+    type Output = Style;
+
+    fn add(mut self, rhs: BackgroundColors) -> Self::Output {
+        self.bg = Some(rhs);
+        self
+    }
+}
+
+impl Add<FontMode> for Style {
+    type Output = Style;
+
+    fn add(self, rhs: FontMode) -> Self::Output {
+        self.add_mode(rhs)
+    }
+}
+
+impl Add<Style> for Style {
+    type Output = Style;
+
+    fn add(mut self, rhs: Style) -> Self::Output {
+        if rhs.fg.is_some() {
+            self.fg = rhs.fg;
+        }
+        if rhs.bg.is_some() {
+            self.bg = rhs.bg;
+        }
+        rhs.modes.into_iter().fold(self, Style::add_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_style() {
+        assert!(Style::new().is_empty());
+        assert_eq!("", Style::new().to_string());
+    }
+
+    #[test]
+    fn test_style_order() {
+        let style = FontMode::underline() + BackgroundColors::blue() + ForegroundColors::red();
+
+        assert_eq!("\x1B[31;44;4m", style.to_string());
+    }
+
+    #[test]
+    fn test_style_replaces_colors() {
+        let style = ForegroundColors::cyan()
+            + BackgroundColors::black()
+            + ForegroundColors::magenta()
+            + BackgroundColors::white();
+
+        assert_eq!("\x1B[35;47m", style.to_string());
+    }
+
+    #[test]
+    fn test_style_deduplicates_modes() {
+        let style = FontMode::bold() + FontMode::italic() + FontMode::bold();
+
+        assert_eq!("\x1B[1;3m", style.to_string());
+    }
+
+    #[test]
+    fn test_style_reset_modes() {
+        let style =
+            FontMode::bold() + FontMode::dim() + FontMode::italic() + FontMode::reset_bold();
+        assert_eq!("\x1B[3m", style.to_string());
+
+        let style = ForegroundColors::red() + FontMode::underline() + FontMode::reset_all();
+        assert!(style.is_empty());
+    }
+
+    #[test]
+    fn test_style_add_style() {
+        let base = ForegroundColors::red() + BackgroundColors::black() + FontMode::bold();
+        let overlay = ForegroundColors::green() + FontMode::italic();
+
+        assert_eq!("\x1B[32;40;1;3m", (base + overlay).to_string());
+    }
+
+    #[test]
+    fn test_style_with_m_in_parameters() {
+        let style = ForegroundColors::rgb(1, 2, 3) + BackgroundColors::indexed(208);
+
+        assert_eq!("\x1B[38;2;1;2;3;48;5;208m", style.to_string());
+    }
+
+    #[test]
+    fn test_style_downsample() {
+        let style =
+            ForegroundColors::rgb(255, 0, 0) + BackgroundColors::indexed(17) + FontMode::bold();
+
+        assert_eq!(
+            "\x1B[38;5;196;48;5;17;1m",
+            style.downsample(ColorSupport::Ansi256).to_string()
+        );
+        assert_eq!("\x1B[1m", style.downsample(ColorSupport::None).to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let style = ForegroundColors::indexed(208) + BackgroundColors::blue() + FontMode::bold();
+
+        assert_eq!(Ok(style.clone()), style.to_string().parse());
+        assert_eq!(Ok(Style::new()), "\x1B[0m".parse());
+        assert_eq!(
+            Ok(ForegroundColors::green().into()),
+            "\x1B[31;1;0;32m".parse::<Style>()
+        );
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!("\x1B[38;5m".parse::<Style>().is_err());
+        assert!("\x1B[6m".parse::<Style>().is_err());
+        assert!("bold".parse::<Style>().is_err());
+    }
+
+    #[test]
+    fn test_apply_parameters() {
+        let style = Style::from(ForegroundColors::red()).apply_parameters(&[1, 53, 44, 39]);
+
+        assert_eq!(
+            ForegroundColors::default() + BackgroundColors::blue() + FontMode::bold(),
+            style
+        );
+        assert!(style.apply_parameters(&[0]).is_empty());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Ok(ForegroundColors::bright_red().into()),
+            Style::from_name("bright_red")
+        );
+        assert_eq!(
+            Ok(BackgroundColors::rgb(0, 0, 95) + FontMode::italic()),
+            Style::from_name("  italic  on   #00005f ")
+        );
+        assert_eq!(Ok(Style::new()), Style::from_name("none"));
+        assert_eq!(Ok(Style::new()), Style::from_name(""));
+    }
+
+    #[test]
+    fn test_from_name_invalid() {
+        assert!(Style::from_name("bold orange").is_err());
+        assert!(Style::from_name("red on").is_err());
+        assert!(Style::from_name("red on bold").is_err());
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        let styles = [
+            Style::new(),
+            ForegroundColors::indexed(208) + BackgroundColors::default() + FontMode::dim(),
+            BackgroundColors::bright_white() + FontMode::reverse() + FontMode::blinking(),
+        ];
+
+        for style in styles {
+            assert_eq!(Ok(style.clone()), Style::from_name(&style.name()));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        use serde::Deserialize;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+
+        let deserializer: StrDeserializer<Error> = "bold red on black".into_deserializer();
+        assert_eq!(
+            Ok(ForegroundColors::red() + BackgroundColors::black() + FontMode::bold()),
+            Style::deserialize(deserializer)
+        );
+
+        let deserializer: StrDeserializer<Error> = "red on nothing".into_deserializer();
+        assert!(Style::deserialize(deserializer).is_err());
+    }
+}
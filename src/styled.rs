@@ -0,0 +1,87 @@
+use crate::style::Style;
+use std::fmt;
+
+/// Value that is printed with a style, followed by a reset
+///
+/// Resets inside the value, for example from another `Styled`, are followed by this style
+/// again, so nested values only change the style of their own part of the text
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::font_mode::FontMode;
+/// use crate::apollo_logger::foreground_colors::ForegroundColors;
+/// use crate::apollo_logger::style::Style;
+///
+/// let user_id = Style::from(FontMode::bold()).paint(42);
+/// let message = Style::from(ForegroundColors::red()).paint(format!("User {user_id} failed"));
+///
+/// assert_eq!("\x1B[31mUser \x1B[1m42\x1B[0m\x1B[31m failed\x1B[0m", message.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Styled<T: fmt::Display> {
+    pub style: Style,
+    pub value: T,
+}
+
+impl<T: fmt::Display> Styled<T> {
+    pub fn new(style: Style, value: T) -> Styled<T> {
+        Styled { style, value }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Styled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.style.is_empty() {
+            return write!(f, "{}", self.value);
+        }
+
+        let style = self.style.to_string();
+        let value = self.value.to_string();
+
+        write!(
+            f,
+            "{style}{}\x1B[0m",
+            value.replace("\x1B[0m", &format!("\x1B[0m{style}"))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::background_colors::BackgroundColors;
+    use crate::font_mode::FontMode;
+    use crate::foreground_colors::ForegroundColors;
+
+    #[test]
+    fn test_paint() {
+        let styled = Style::from(ForegroundColors::green()).paint("ok");
+
+        assert_eq!("\x1B[32mok\x1B[0m", styled.to_string());
+    }
+
+    #[test]
+    fn test_paint_empty_style() {
+        assert_eq!("plain", Style::new().paint("plain").to_string());
+    }
+
+    #[test]
+    fn test_paint_display_value() {
+        let styled = Style::from(FontMode::underline()).paint(3.5);
+
+        assert_eq!("\x1B[4m3.5\x1B[0m", styled.to_string());
+    }
+
+    #[test]
+    fn test_nested_restores_outer_style() {
+        let inner = (ForegroundColors::yellow() + FontMode::bold()).paint("inner");
+        let middle = Style::from(BackgroundColors::blue()).paint(format!("a {inner} b"));
+        let outer = Style::from(FontMode::italic()).paint(format!("[{middle}]"));
+
+        assert_eq!(
+            "\x1B[3m[\x1B[44ma \x1B[33;1minner\x1B[0m\x1B[3m\x1B[44m b\x1B[0m\x1B[3m]\x1B[0m",
+            outer.to_string()
+        );
+    }
+}
@@ -0,0 +1,587 @@
+use crate::levels::Levels;
+use crate::record::Record;
+use crate::sink::Sink;
+use chrono::{Local, SecondsFormat};
+use std::io;
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Time to wait for a TCP connection or write before giving up
+const TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time to wait before reconnecting after the first failed connection, doubled after every
+/// further failure up to [`MAX_RECONNECT_BACKOFF`]
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Kind of program that logs the messages, as syslog servers use it to route them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Facility {
+    Kern,
+    User,
+    Mail,
+    Daemon,
+    Auth,
+    Syslog,
+    Lpr,
+    News,
+    Uucp,
+    Cron,
+    Authpriv,
+    Ftp,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl Facility {
+    /// Gets the numeric code of the facility
+    pub fn code(&self) -> u8 {
+        match self {
+            Facility::Kern => 0,
+            Facility::User => 1,
+            Facility::Mail => 2,
+            Facility::Daemon => 3,
+            Facility::Auth => 4,
+            Facility::Syslog => 5,
+            Facility::Lpr => 6,
+            Facility::News => 7,
+            Facility::Uucp => 8,
+            Facility::Cron => 9,
+            Facility::Authpriv => 10,
+            Facility::Ftp => 11,
+            Facility::Local0 => 16,
+            Facility::Local1 => 17,
+            Facility::Local2 => 18,
+            Facility::Local3 => 19,
+            Facility::Local4 => 20,
+            Facility::Local5 => 21,
+            Facility::Local6 => 22,
+            Facility::Local7 => 23,
+        }
+    }
+}
+
+/// Format of the messages sent to the syslog server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFormat {
+    /// Current format, like `<11>1 2025-06-07T08:09:10.000Z host app 42 db - failed retries=3`
+    Rfc5424,
+    /// Legacy BSD format in local time, like `<11>Jun  7 10:09:10 host app[42]: failed retries=3`
+    Rfc3164,
+}
+
+/// Gets the syslog severity of a level, from 2 for CRITICAL up to 7 for DEBUG
+pub fn severity(level: Levels) -> u8 {
+    match level {
+        Levels::CRITICAL => 2,
+        Levels::ERROR => 3,
+        Levels::WARN => 4,
+        Levels::INFO => 6,
+        Levels::DEBUG | Levels::NONE => 7,
+    }
+}
+
+/// Socket the messages are sent over
+enum Connection {
+    #[cfg(unix)]
+    Unix(UnixDatagram),
+    Udp(UdpSocket),
+    Tcp(Tcp),
+}
+
+impl Connection {
+    fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(socket) => socket.send(frame).map(|_| ()),
+            Connection::Udp(socket) => socket.send(frame).map(|_| ()),
+            Connection::Tcp(tcp) => tcp.send(frame),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Tcp(Tcp {
+                stream: Some(stream),
+                ..
+            }) => stream.flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Connection to a server over TCP, which is reopened when it breaks
+struct Tcp {
+    address: SocketAddr,
+    stream: Option<TcpStream>,
+    /// Time before which no new connection is tried, after connecting failed
+    retry_at: Option<Instant>,
+    /// Time to wait after the next failed connection
+    backoff: Duration,
+}
+
+impl Tcp {
+    fn new(address: SocketAddr, stream: Option<TcpStream>) -> Tcp {
+        Tcp {
+            address,
+            stream,
+            retry_at: None,
+            backoff: MIN_RECONNECT_BACKOFF,
+        }
+    }
+
+    /// Connects to the server, connecting and writing time out so a server that does not
+    /// respond can not block the logger
+    fn connect(address: SocketAddr) -> io::Result<TcpStream> {
+        let stream = TcpStream::connect_timeout(&address, TCP_TIMEOUT)?;
+        stream.set_write_timeout(Some(TCP_TIMEOUT))?;
+
+        Ok(stream)
+    }
+
+    fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        if let Some(stream) = &mut self.stream {
+            if stream.write_all(frame).is_ok() {
+                return Ok(());
+            }
+            self.stream = None;
+        }
+
+        // The server may have restarted, try once with a new connection unless
+        // connecting failed recently
+        let now = Instant::now();
+        if self.retry_at.is_some_and(|retry_at| now < retry_at) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "waiting to reconnect to the syslog server",
+            ));
+        }
+
+        let result = Self::connect(self.address).and_then(|mut stream| {
+            stream.write_all(frame)?;
+            Ok(stream)
+        });
+        match result {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                self.retry_at = None;
+                self.backoff = MIN_RECONNECT_BACKOFF;
+                Ok(())
+            }
+            Err(error) => {
+                self.retry_at = Some(now + self.backoff);
+                self.backoff = (self.backoff * 2).min(MAX_RECONNECT_BACKOFF);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Sink that sends every record to a syslog server, like rsyslog or journald
+///
+/// Records are sent in the RFC 5424 format with the `user` facility by default, or in the
+/// RFC 3164 format to the local daemon,
+/// the app-name is the name of the executable and the logger name is the MSGID.
+/// Fields follow the message in logfmt
+///
+/// # Examples
+///
+/// ```no_run
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::syslog::{Facility, SyslogSink};
+/// use std::sync::Arc;
+///
+/// let syslog = SyslogSink::udp("logs.example.com:514")
+///     .unwrap()
+///     .with_facility(Facility::Local0)
+///     .with_app_name("billing");
+/// let l = Apollo { sinks: vec![Arc::new(syslog)], ..Apollo::new() };
+///
+/// l.error("Payment provider unreachable");
+/// ```
+pub struct SyslogSink {
+    connection: Mutex<Connection>,
+    format: SyslogFormat,
+    facility: Facility,
+    app_name: String,
+    hostname: String,
+    process_id: u32,
+}
+
+impl SyslogSink {
+    /// Sends records to the local syslog daemon through `/dev/log`
+    ///
+    /// The records are in the RFC 3164 format like the ones of `syslog()` in libc,
+    /// as journald and rsyslog expect that format on the local socket
+    #[cfg(unix)]
+    pub fn local() -> io::Result<SyslogSink> {
+        SyslogSink::local_at("/dev/log")
+    }
+
+    /// Sends records to a local syslog daemon listening on a Unix datagram socket
+    #[cfg(unix)]
+    fn local_at(path: impl AsRef<Path>) -> io::Result<SyslogSink> {
+        Ok(SyslogSink::unix(path)?.with_format(SyslogFormat::Rfc3164))
+    }
+
+    /// Sends records to a Unix datagram socket
+    #[cfg(unix)]
+    pub fn unix(path: impl AsRef<Path>) -> io::Result<SyslogSink> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(path)?;
+
+        Ok(SyslogSink::new(Connection::Unix(socket)))
+    }
+
+    /// Sends records over UDP, every record is a single datagram
+    pub fn udp(address: impl ToSocketAddrs) -> io::Result<SyslogSink> {
+        let address = Self::resolve(address)?;
+        let local: SocketAddr = if address.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(address)?;
+
+        Ok(SyslogSink::new(Connection::Udp(socket)))
+    }
+
+    /// Sends records over TCP, framed with their length for RFC 5424 and with a newline for RFC 3164
+    pub fn tcp(address: impl ToSocketAddrs) -> io::Result<SyslogSink> {
+        let address = Self::resolve(address)?;
+        let stream = Tcp::connect(address)?;
+
+        Ok(SyslogSink::new(Connection::Tcp(Tcp::new(
+            address,
+            Some(stream),
+        ))))
+    }
+
+    fn new(connection: Connection) -> SyslogSink {
+        SyslogSink {
+            connection: Mutex::new(connection),
+            format: SyslogFormat::Rfc5424,
+            facility: Facility::User,
+            app_name: Self::default_app_name(),
+            hostname: Self::default_hostname(),
+            process_id: std::process::id(),
+        }
+    }
+
+    pub fn with_format(mut self, format: SyslogFormat) -> SyslogSink {
+        self.format = format;
+        self
+    }
+
+    pub fn with_facility(mut self, facility: Facility) -> SyslogSink {
+        self.facility = facility;
+        self
+    }
+
+    pub fn with_app_name(mut self, app_name: &str) -> SyslogSink {
+        self.app_name = app_name.to_string();
+        self
+    }
+
+    pub fn with_hostname(mut self, hostname: &str) -> SyslogSink {
+        self.hostname = hostname.to_string();
+        self
+    }
+
+    /// Converts a record to a syslog message in the format of this sink, without framing
+    ///
+    /// Newlines in the message are escaped as `\n`, as many servers split messages at newlines
+    pub fn format(&self, record: &Record) -> String {
+        let priority = self.facility.code() * 8 + severity(record.level);
+        let mut message = record.message.clone();
+        for field in &record.fields {
+            message.push(' ');
+            message.push_str(&field.to_string());
+        }
+        let message = message.replace('\n', "\\n");
+
+        match self.format {
+            SyslogFormat::Rfc5424 => format!(
+                "<{priority}>1 {} {} {} {} {} - {message}",
+                record.time.to_rfc3339_opts(SecondsFormat::Millis, true),
+                Self::header(&self.hostname, 255),
+                Self::header(&self.app_name, 48),
+                self.process_id,
+                Self::header(&record.logger, 32)
+            ),
+            SyslogFormat::Rfc3164 => format!(
+                "<{priority}>{} {} {}[{}]: {message}",
+                record.time.with_timezone(&Local).format("%b %e %H:%M:%S"),
+                Self::header(&self.hostname, 255),
+                Self::header(&self.app_name, 32),
+                self.process_id,
+            ),
+        }
+    }
+
+    /// Makes a header field printable ASCII without spaces, `-` stands for an empty field
+    fn header(s: &str, max: usize) -> String {
+        let header: String = s
+            .chars()
+            .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+            .take(max)
+            .collect();
+
+        if header.is_empty() {
+            String::from("-")
+        } else {
+            header
+        }
+    }
+
+    fn resolve(address: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+        address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "address resolved to nothing")
+        })
+    }
+
+    /// Gets the name of the executable
+    fn default_app_name() -> String {
+        std::env::current_exe()
+            .ok()
+            .and_then(|path| {
+                path.file_stem()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the name of this machine, from the environment or the kernel
+    fn default_hostname() -> String {
+        std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .or_else(|| std::env::var("COMPUTERNAME").ok())
+            .map(|hostname| hostname.trim().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Sink for SyslogSink {
+    fn log(&self, record: &Record) -> io::Result<()> {
+        let mut connection = self.connection.lock().unwrap();
+        let message = self.format(record);
+
+        let frame = match (&*connection, self.format) {
+            (Connection::Tcp(..), SyslogFormat::Rfc5424) => {
+                format!("{} {message}", message.len())
+            }
+            (Connection::Tcp(..), SyslogFormat::Rfc3164) => format!("{message}\n"),
+            _ => message,
+        };
+        connection.send(frame.as_bytes())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.connection.lock().unwrap().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+    use chrono::{TimeZone, Utc};
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    fn record(level: Levels) -> Record {
+        let time = Utc.with_ymd_and_hms(2025, 6, 7, 8, 9, 10).unwrap();
+
+        Record {
+            logger: String::from("db pool"),
+            location: String::from("db.rs:88"),
+            fields: vec![Field::new("retries", 3)],
+            ..Record::new(level, time, "failed")
+        }
+    }
+
+    fn udp_pair() -> (UdpSocket, SyslogSink) {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sink = SyslogSink::udp(server.local_addr().unwrap())
+            .unwrap()
+            .with_hostname("web-1")
+            .with_app_name("billing");
+
+        (server, sink)
+    }
+
+    fn receive(server: &UdpSocket) -> String {
+        let mut buffer = [0; 1024];
+        let length = server.recv(&mut buffer).unwrap();
+
+        String::from_utf8_lossy(&buffer[..length]).into_owned()
+    }
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(7, severity(Levels::DEBUG));
+        assert_eq!(6, severity(Levels::INFO));
+        assert_eq!(4, severity(Levels::WARN));
+        assert_eq!(3, severity(Levels::ERROR));
+        assert_eq!(2, severity(Levels::CRITICAL));
+    }
+
+    #[test]
+    fn test_rfc5424_over_udp() {
+        let (server, sink) = udp_pair();
+        let sink = sink.with_facility(Facility::Local0);
+
+        sink.log(&record(Levels::ERROR)).unwrap();
+
+        assert_eq!(
+            format!(
+                "<131>1 2025-06-07T08:09:10.000Z web-1 billing {} db_pool - failed retries=3",
+                std::process::id()
+            ),
+            receive(&server)
+        );
+    }
+
+    #[test]
+    fn test_rfc3164_over_udp() {
+        let (server, sink) = udp_pair();
+        let sink = sink.with_format(SyslogFormat::Rfc3164);
+
+        sink.log(&record(Levels::WARN)).unwrap();
+
+        let message = receive(&server);
+        assert!(message.starts_with("<12>Jun "));
+        assert!(message.ends_with(&format!(
+            " web-1 billing[{}]: failed retries=3",
+            std::process::id()
+        )));
+    }
+
+    #[test]
+    fn test_tcp_framing() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let sink = SyslogSink::tcp(server.local_addr().unwrap())
+            .unwrap()
+            .with_hostname("web-1")
+            .with_app_name("billing");
+        let (mut connection, _) = server.accept().unwrap();
+
+        sink.log(&record(Levels::CRITICAL)).unwrap();
+        drop(sink);
+
+        let mut frame = String::new();
+        connection.read_to_string(&mut frame).unwrap();
+        let (length, message) = frame.split_once(' ').unwrap();
+        assert_eq!(message.len(), length.parse::<usize>().unwrap());
+        assert!(message.starts_with("<10>1 2025-06-07T08:09:10.000Z web-1 billing "));
+    }
+
+    #[test]
+    fn test_newlines_are_escaped() {
+        let record = Record {
+            message: String::from("failed\nforged line"),
+            ..record(Levels::ERROR)
+        };
+
+        for format in [SyslogFormat::Rfc5424, SyslogFormat::Rfc3164] {
+            let (_, sink) = udp_pair();
+            let message = sink.with_format(format).format(&record);
+
+            assert!(!message.contains('\n'));
+            assert!(message.ends_with(" failed\\nforged line retries=3"));
+        }
+    }
+
+    #[test]
+    fn test_tcp_reconnect_backoff() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        drop(server);
+        let mut tcp = Tcp::new(address, None);
+
+        assert_ne!(
+            io::ErrorKind::NotConnected,
+            tcp.send(b"first").unwrap_err().kind()
+        );
+        assert_eq!(
+            io::ErrorKind::NotConnected,
+            tcp.send(b"second").unwrap_err().kind()
+        );
+        assert_eq!(MIN_RECONNECT_BACKOFF * 2, tcp.backoff);
+
+        let server = TcpListener::bind(address).unwrap();
+        tcp.retry_at = Some(Instant::now());
+        tcp.send(b"third").unwrap();
+        assert_eq!(MIN_RECONNECT_BACKOFF, tcp.backoff);
+
+        let (mut connection, _) = server.accept().unwrap();
+        drop(tcp);
+        let mut received = String::new();
+        connection.read_to_string(&mut received).unwrap();
+        assert_eq!("third", received);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_socket() {
+        let path = std::env::temp_dir().join(format!("apollo-syslog-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let server = UnixDatagram::bind(&path).unwrap();
+        let sink = SyslogSink::unix(&path).unwrap();
+
+        sink.log(&record(Levels::DEBUG)).unwrap();
+
+        let mut buffer = [0; 1024];
+        let length = server.recv(&mut buffer).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(String::from_utf8_lossy(&buffer[..length]).starts_with("<15>1 "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_local_format() {
+        let path =
+            std::env::temp_dir().join(format!("apollo-syslog-local-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let server = UnixDatagram::bind(&path).unwrap();
+        let sink = SyslogSink::local_at(&path)
+            .unwrap()
+            .with_hostname("web-1")
+            .with_app_name("billing");
+
+        sink.log(&record(Levels::INFO)).unwrap();
+
+        let mut buffer = [0; 1024];
+        let length = server.recv(&mut buffer).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let message = String::from_utf8_lossy(&buffer[..length]).into_owned();
+        assert!(message.starts_with("<14>Jun "));
+        assert!(message.ends_with(&format!(
+            " web-1 billing[{}]: failed retries=3",
+            std::process::id()
+        )));
+    }
+
+    #[test]
+    fn test_empty_header() {
+        assert_eq!("-", SyslogSink::header("", 32));
+        assert_eq!("ab", SyslogSink::header("abc", 2));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::record::Record;
+use crate::sink::Sink;
+use std::io;
+use std::sync::Mutex;
+
+/// Gets a lookup function for environment variables that only knows the given variables
+pub fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+    move |key| {
+        vars.iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+/// Sink that keeps all records in memory
+#[derive(Default)]
+pub struct MemorySink {
+    pub records: Mutex<Vec<Record>>,
+}
+
+impl Sink for MemorySink {
+    fn log(&self, record: &Record) -> io::Result<()> {
+        self.records.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+}
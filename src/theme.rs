@@ -0,0 +1,267 @@
+use crate::background_colors::BackgroundColors;
+use crate::font_mode::FontMode;
+use crate::foreground_colors::ForegroundColors;
+use crate::levels::Levels;
+use crate::style::Style;
+
+/// Styles used for the different parts of a log line of a single level
+///
+/// An empty style leaves that part of the line unstyled
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelTheme {
+    pub date: Style,
+    pub label: Style,
+    pub location: Style,
+    pub message: Style,
+    /// Style of the `key=value` fields after the message
+    pub fields: Style,
+}
+
+impl LevelTheme {
+    /// Creates a level theme without any styles
+    pub fn plain() -> LevelTheme {
+        LevelTheme {
+            date: Style::new(),
+            label: Style::new(),
+            location: Style::new(),
+            message: Style::new(),
+            fields: Style::new(),
+        }
+    }
+}
+
+/// Styles for every level Apollo can log at
+///
+/// With the `serde` feature a theme can be loaded from a config file,
+/// every style is written by name like `label = "bold white on red"`
+///
+/// # Examples
+///
+/// ```
+/// use crate::apollo_logger::Apollo;
+/// use crate::apollo_logger::font_mode::FontMode;
+/// use crate::apollo_logger::foreground_colors::ForegroundColors;
+/// use crate::apollo_logger::theme::Theme;
+///
+/// let mut theme = Theme::high_contrast();
+/// theme.info.message = ForegroundColors::magenta() + FontMode::italic();
+///
+/// let l = Apollo { theme, ..Apollo::new() };
+///
+/// l.info("This message will be printed in italic magenta");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Theme {
+    pub debug: LevelTheme,
+    pub info: LevelTheme,
+    pub warn: LevelTheme,
+    pub error: LevelTheme,
+    pub critical: LevelTheme,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Theme {
+    /// Creates the default Apollo theme
+    pub fn new() -> Theme {
+        let level = |label: ForegroundColors, message: Style| LevelTheme {
+            date: ForegroundColors::bright_green().into(),
+            label: label.into(),
+            location: FontMode::italic().into(),
+            message,
+            fields: FontMode::dim().into(),
+        };
+
+        Theme {
+            debug: level(
+                ForegroundColors::cyan(),
+                ForegroundColors::bright_cyan().into(),
+            ),
+            info: level(
+                ForegroundColors::blue(),
+                ForegroundColors::bright_white().into(),
+            ),
+            warn: level(
+                ForegroundColors::yellow(),
+                ForegroundColors::yellow() + FontMode::bold(),
+            ),
+            error: level(
+                ForegroundColors::red(),
+                ForegroundColors::red() + FontMode::bold(),
+            ),
+            critical: level(
+                ForegroundColors::bright_red(),
+                ForegroundColors::bright_white()
+                    + BackgroundColors::bright_red()
+                    + FontMode::bold()
+                    + FontMode::underline(),
+            ),
+        }
+    }
+
+    /// Creates a theme without any colors, levels are only told apart by their font mode
+    pub fn monochrome() -> Theme {
+        let level = |label: Style, message: Style| LevelTheme {
+            date: FontMode::dim().into(),
+            label,
+            location: FontMode::italic().into(),
+            message,
+            fields: FontMode::dim().into(),
+        };
+
+        Theme {
+            debug: level(FontMode::dim().into(), FontMode::dim().into()),
+            info: level(Style::new(), Style::new()),
+            warn: level(FontMode::bold().into(), FontMode::bold().into()),
+            error: level(
+                FontMode::bold() + FontMode::reverse(),
+                FontMode::bold() + FontMode::underline(),
+            ),
+            critical: level(
+                FontMode::bold() + FontMode::reverse(),
+                FontMode::bold() + FontMode::underline() + FontMode::reverse(),
+            ),
+        }
+    }
+
+    /// Creates a theme with bright colors and colored label backgrounds, for readability
+    pub fn high_contrast() -> Theme {
+        let level = |label: BackgroundColors, message: Style| LevelTheme {
+            date: ForegroundColors::bright_white().into(),
+            label: ForegroundColors::black() + label + FontMode::bold(),
+            location: ForegroundColors::bright_white() + FontMode::underline(),
+            message,
+            fields: ForegroundColors::bright_white().into(),
+        };
+
+        Theme {
+            debug: level(
+                BackgroundColors::bright_cyan(),
+                ForegroundColors::bright_cyan().into(),
+            ),
+            info: level(
+                BackgroundColors::bright_white(),
+                ForegroundColors::bright_white().into(),
+            ),
+            warn: level(
+                BackgroundColors::bright_yellow(),
+                ForegroundColors::bright_yellow() + FontMode::bold(),
+            ),
+            error: level(
+                BackgroundColors::bright_red(),
+                ForegroundColors::bright_red() + FontMode::bold(),
+            ),
+            critical: level(
+                BackgroundColors::bright_magenta(),
+                ForegroundColors::bright_white()
+                    + BackgroundColors::red()
+                    + FontMode::bold()
+                    + FontMode::underline(),
+            ),
+        }
+    }
+
+    /// Creates a theme for terminals using the Solarized color palette
+    pub fn solarized() -> Theme {
+        // Solarized maps its accent colors onto the 16 ANSI colors, bright green and
+        // bright blue are the content tones base01 and base0. Bright black is base03,
+        // the dark background, so it is not used
+        let level = |label: ForegroundColors, message: ForegroundColors| LevelTheme {
+            date: ForegroundColors::bright_green().into(),
+            label: label + FontMode::bold(),
+            location: ForegroundColors::bright_blue() + FontMode::italic(),
+            message: message.into(),
+            fields: ForegroundColors::bright_green().into(),
+        };
+
+        Theme {
+            debug: level(ForegroundColors::cyan(), ForegroundColors::bright_cyan()),
+            info: level(ForegroundColors::blue(), ForegroundColors::bright_blue()),
+            warn: level(ForegroundColors::yellow(), ForegroundColors::yellow()),
+            error: level(
+                ForegroundColors::bright_red(),
+                ForegroundColors::bright_red(),
+            ),
+            critical: level(ForegroundColors::red(), ForegroundColors::magenta()),
+        }
+    }
+
+    /// Gets the styles for the given level, `Levels::NONE` has no styles
+    pub fn get(&self, level: Levels) -> Option<&LevelTheme> {
+        match level {
+            Levels::DEBUG => Some(&self.debug),
+            Levels::INFO => Some(&self.info),
+            Levels::WARN => Some(&self.warn),
+            Levels::ERROR => Some(&self.error),
+            Levels::CRITICAL => Some(&self.critical),
+            Levels::NONE => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme() {
+        let theme = Theme::default();
+
+        assert_eq!("\x1B[92m", theme.debug.date.to_string());
+        assert_eq!("\x1B[36m", theme.debug.label.to_string());
+        assert_eq!("\x1B[3m", theme.debug.location.to_string());
+        assert_eq!("\x1B[33;1m", theme.warn.message.to_string());
+        assert_eq!("\x1B[97;101;1;4m", theme.critical.message.to_string());
+    }
+
+    #[test]
+    fn test_monochrome_has_no_colors() {
+        let theme = Theme::monochrome();
+
+        for level in [
+            &theme.debug,
+            &theme.info,
+            &theme.warn,
+            &theme.error,
+            &theme.critical,
+        ] {
+            for style in [&level.date, &level.label, &level.location, &level.message] {
+                assert!(style.fg.is_none() && style.bg.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let theme = Theme::solarized();
+
+        assert_eq!(Some(&theme.info), theme.get(Levels::INFO));
+        assert_eq!(Some(&theme.critical), theme.get(Levels::CRITICAL));
+        assert_eq!(None, theme.get(Levels::NONE));
+    }
+
+    #[test]
+    fn test_solarized_avoids_background_tone() {
+        let theme = Theme::solarized();
+
+        for level_theme in [
+            &theme.debug,
+            &theme.info,
+            &theme.warn,
+            &theme.error,
+            &theme.critical,
+        ] {
+            assert_ne!(
+                Some(ForegroundColors::bright_black()),
+                level_theme.location.fg
+            );
+            assert_ne!(Some(ForegroundColors::bright_black()), level_theme.date.fg);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Which timestamp(s) to print in front of every log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeMode {
+    /// Date and time of day, e.g. `[06/29/25 16:49:08.123]`
+    WallClock,
+    /// Time since the logger was created, e.g. `[+12.345s]`
+    Elapsed,
+    /// Time since the previous record of the same logger, e.g. `[+0.012s]`
+    SincePrevious,
+    /// Wall-clock time followed by the time since the logger was created
+    WallClockAndElapsed,
+    /// Wall-clock time followed by the time since the previous record
+    WallClockAndSincePrevious,
+}
+
+impl TimeMode {
+    pub fn shows_wall_clock(&self) -> bool {
+        matches!(
+            self,
+            Self::WallClock | Self::WallClockAndElapsed | Self::WallClockAndSincePrevious
+        )
+    }
+
+    pub fn shows_elapsed(&self) -> bool {
+        matches!(self, Self::Elapsed | Self::WallClockAndElapsed)
+    }
+
+    pub fn shows_since_previous(&self) -> bool {
+        matches!(self, Self::SincePrevious | Self::WallClockAndSincePrevious)
+    }
+}
+
+/// Remembers when the previous record of a logger was printed
+#[derive(Debug, Default)]
+pub struct PreviousRecord {
+    elapsed: Mutex<Option<Duration>>,
+}
+
+impl PreviousRecord {
+    pub fn new() -> PreviousRecord {
+        PreviousRecord::default()
+    }
+
+    /// Stores the elapsed time of the current record and returns the one of the previous record
+    pub fn replace(&self, elapsed: Duration) -> Option<Duration> {
+        self.elapsed.lock().unwrap().replace(elapsed)
+    }
+
+    /// Gets the elapsed time of the previous record, without replacing it
+    pub fn get(&self) -> Option<Duration> {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+/// Formats a duration as seconds with millisecond precision, e.g. `+12.345s`
+pub fn format_relative(duration: Duration) -> String {
+    format!("+{}.{:03}s", duration.as_secs(), duration.subsec_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative() {
+        assert_eq!("+0.000s", format_relative(Duration::ZERO));
+        assert_eq!("+12.345s", format_relative(Duration::from_millis(12345)));
+        assert_eq!(
+            "+3600.007s",
+            format_relative(Duration::from_micros(3_600_007_900))
+        );
+    }
+
+    #[test]
+    fn test_previous_record_replace() {
+        let previous = PreviousRecord::new();
+
+        assert_eq!(None, previous.replace(Duration::from_secs(1)));
+        assert_eq!(Some(Duration::from_secs(1)), previous.get());
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            previous.replace(Duration::from_secs(3))
+        );
+    }
+
+    #[test]
+    fn test_shows() {
+        assert!(TimeMode::WallClock.shows_wall_clock());
+        assert!(!TimeMode::WallClock.shows_elapsed());
+        assert!(TimeMode::WallClockAndElapsed.shows_elapsed());
+        assert!(TimeMode::WallClockAndSincePrevious.shows_since_previous());
+        assert!(!TimeMode::SincePrevious.shows_wall_clock());
+    }
+}